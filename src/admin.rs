@@ -0,0 +1,183 @@
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use http_body_util::{BodyExt as _, Full, Limited};
+use hyper::{body::Incoming, Method, Request, Response, StatusCode};
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{
+    http_server::{response, Handler, PLAIN_TEXT},
+    serde::deserialize_optional_seconds,
+    service::{ManualOverride, OverrideMode},
+};
+
+const MAX_REQUEST_BYTES: usize = 64 << 10;
+
+const MAX_EXPIRES_IN: Duration = Duration::from_secs(366 * 24 * 60 * 60);
+
+pub(crate) struct OverrideRequest {
+    pub service_name: Box<str>,
+    pub instance_name: Box<str>,
+    pub manual_override: Option<ManualOverride>,
+    pub respond: oneshot::Sender<OverrideOutcome>,
+}
+
+pub(crate) enum OverrideOutcome {
+    Applied,
+    NotFound,
+    NotWrittenOut(Box<str>),
+}
+
+#[derive(Clone)]
+pub(crate) struct Admin {
+    requests: mpsc::Sender<OverrideRequest>,
+}
+
+impl Admin {
+    #[inline]
+    pub const fn new(requests: mpsc::Sender<OverrideRequest>) -> Self {
+        Self { requests }
+    }
+
+    async fn set_override(
+        &self,
+        request: Request<Incoming>,
+    ) -> Result<&'static str, (StatusCode, String)> {
+        let body = Limited::new(request.into_body(), MAX_REQUEST_BYTES)
+            .collect()
+            .await
+            .map_err(|error| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read request body! Cause: {error}\n"),
+                )
+            })?
+            .to_bytes();
+
+        let Body {
+            service,
+            instance,
+            mode,
+            expires_in,
+        } = serde_json::from_slice(&body).map_err(|error| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to parse request body! Cause: {error}\n"),
+            )
+        })?;
+
+        let expires_at = expires_in
+            .map(|expires_in| {
+                if expires_in <= MAX_EXPIRES_IN {
+                    SystemTime::now().checked_add(expires_in)
+                } else {
+                    None
+                }
+                .ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        format!(
+                            "Expiry is out of range! It can be at most {} \
+                            seconds.\n",
+                            MAX_EXPIRES_IN.as_secs(),
+                        ),
+                    )
+                })
+            })
+            .transpose()?;
+
+        let manual_override = match (mode, expires_at) {
+            (Some(mode), expires_at) => {
+                Some(ManualOverride { mode, expires_at })
+            }
+            (None, None) => None,
+            (None, Some(_)) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "An expiry can only be set along with a mode!\n".into(),
+                ));
+            }
+        };
+
+        let (respond, response) = oneshot::channel();
+
+        let sent = self
+            .requests
+            .send(OverrideRequest {
+                service_name: service,
+                instance_name: instance,
+                manual_override,
+                respond,
+            })
+            .await;
+
+        match (sent, response.await) {
+            (Ok(()), Ok(OverrideOutcome::Applied))
+                if manual_override.is_some() =>
+            {
+                Ok("Manual override applied.\n")
+            }
+            (Ok(()), Ok(OverrideOutcome::Applied)) => {
+                Ok("Manual override cleared.\n")
+            }
+            (Ok(()), Ok(OverrideOutcome::NotFound)) => Err((
+                StatusCode::NOT_FOUND,
+                "No such service instance.\n".into(),
+            )),
+            (Ok(()), Ok(OverrideOutcome::NotWrittenOut(error))) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!(
+                    "Manual override recorded, but writing out the output \
+                    configuration failed! It will be retried on the next \
+                    cycle. Cause: {error}\n",
+                ),
+            )),
+            (Err(_), _) | (_, Err(_)) => Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service is shutting down.\n".into(),
+            )),
+        }
+    }
+}
+
+impl Handler for Admin {
+    async fn handle(
+        &self,
+        request: Request<Incoming>,
+    ) -> Response<Full<Bytes>> {
+        match (request.method(), request.uri().path()) {
+            (&Method::POST, "/overrides") => {
+                match self.set_override(request).await {
+                    Ok(message) => {
+                        response(StatusCode::OK, PLAIN_TEXT, message)
+                    }
+                    Err((status, message)) => {
+                        response(status, PLAIN_TEXT, message)
+                    }
+                }
+            }
+            (_, "/overrides") => response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                PLAIN_TEXT,
+                "Method not allowed.\n",
+            ),
+            _ => response(StatusCode::NOT_FOUND, PLAIN_TEXT, "Not found.\n"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Body {
+    service: Box<str>,
+    instance: Box<str>,
+    #[serde(default)]
+    mode: Option<OverrideMode>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_seconds",
+        rename = "expires_in_seconds"
+    )]
+    expires_in: Option<Duration>,
+}
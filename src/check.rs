@@ -0,0 +1,123 @@
+use std::{path::Path, process::ExitCode};
+
+use anyhow::{Context as _, Result};
+use tracing::{info, warn};
+
+use crate::{
+    service::Status,
+    snapshot::{InstanceSnapshot, ServiceSnapshot},
+    state::State,
+    status,
+};
+
+const UNHEALTHY_EXIT_CODE: u8 = 2;
+
+const HEADERS: [&str; 5] =
+    ["SERVICE", "INSTANCE", "STATUS", "LATENCY", "DETAILS"];
+
+pub(crate) async fn run(
+    services_configuration: &Path,
+    json: bool,
+) -> Result<ExitCode> {
+    let services = State::check(services_configuration).await?;
+
+    if json {
+        println!(
+            "{}",
+            status::render_services(&services)
+                .context("Failed to render status!")?,
+        );
+    } else {
+        print!("{}", render_table(&services));
+    }
+
+    let unhealthy_services = services
+        .iter()
+        .filter(|service| service.healthy_instances() == 0)
+        .inspect(|service| {
+            warn!("{:?} has no healthy instances!", service.service_name);
+        })
+        .count();
+
+    Ok(if unhealthy_services == 0 {
+        info!("All services have healthy instances.");
+
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(UNHEALTHY_EXIT_CODE)
+    })
+}
+
+fn render_table(services: &[ServiceSnapshot]) -> String {
+    let rows = services
+        .iter()
+        .flat_map(|service| {
+            service
+                .instances
+                .iter()
+                .map(|instance| row(&service.service_name, instance))
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = HEADERS.map(str::len);
+
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+
+    for row in [HEADERS.map(String::from)].iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        table.push_str(line.trim_end());
+
+        table.push('\n');
+    }
+
+    table
+}
+
+fn row(service_name: &str, instance: &InstanceSnapshot) -> [String; 5] {
+    [
+        service_name.into(),
+        instance.instance_name.to_string(),
+        match instance.status {
+            Status::Enabled => "UP",
+            Status::Disabled => "DOWN",
+        }
+        .into(),
+        instance.statistics.last_check().map_or_else(
+            || "-".into(),
+            |last_check| {
+                format!("{:.1} ms", last_check.latency.as_secs_f64() * 1000.0)
+            },
+        ),
+        details(instance),
+    ]
+}
+
+fn details(instance: &InstanceSnapshot) -> String {
+    if let Some(manual_override) = &instance.manual_override {
+        format!("manual override: {}", manual_override.mode)
+    } else if let Some(maintenance_file) = &instance.maintenance_file {
+        format!("maintenance file present: {}", maintenance_file.display())
+    } else if instance.consecutive_failures != 0
+        || matches!(instance.status, Status::Disabled)
+    {
+        instance
+            .last_failure
+            .as_ref()
+            .map(|failure| failure.to_string().replace(['\r', '\n'], " "))
+            .unwrap_or_default()
+    } else {
+        String::new()
+    }
+}
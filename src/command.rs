@@ -0,0 +1,110 @@
+use std::{
+    path::Path,
+    process::{ExitStatus, Output, Stdio},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::{
+    de::{Deserializer, Error as DeserializeError},
+    Deserialize,
+};
+use tokio::{process::Command as TokioCommand, time::timeout};
+
+use crate::serde::deserialize_seconds;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct Command {
+    #[serde(deserialize_with = "deserialize_arguments")]
+    command: Box<[Box<str>]>,
+    #[serde(
+        default = "default_timeout",
+        deserialize_with = "deserialize_seconds",
+        rename = "timeout_seconds"
+    )]
+    timeout: Duration,
+    #[serde(default = "default_success_exit_codes")]
+    success_exit_codes: Box<[i32]>,
+}
+
+impl Command {
+    pub const CANDIDATE_PLACEHOLDER: &'static str = "{candidate}";
+
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new<const N: usize>(command: [&str; N], timeout: Duration) -> Self {
+        const {
+            assert!(N != 0, "Command requires at least the program's name!");
+        }
+
+        Self {
+            command: command.into_iter().map(Into::into).collect(),
+            timeout,
+            success_exit_codes: default_success_exit_codes(),
+        }
+    }
+
+    pub fn succeeded(&self, status: ExitStatus) -> bool {
+        status
+            .code()
+            .is_some_and(|code| self.success_exit_codes.contains(&code))
+    }
+
+    pub async fn run(&self, placeholders: &[(&str, &Path)]) -> Result<Output> {
+        let (program, arguments) = self
+            .command
+            .split_first()
+            .ok_or_else(|| anyhow!("Command is missing the program's name!"))?;
+
+        let output = TokioCommand::new(&**program)
+            .args(arguments.iter().map(|argument| {
+                placeholders.iter().fold(
+                    String::from(&**argument),
+                    |argument, &(placeholder, value)| {
+                        argument.replace(placeholder, &value.to_string_lossy())
+                    },
+                )
+            }))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output();
+
+        timeout(self.timeout, output)
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "Command {program:?} did not exit within {} seconds!",
+                    self.timeout.as_secs(),
+                )
+            })?
+            .with_context(|| format!("Failed to invoke command {program:?}!"))
+    }
+}
+
+fn deserialize_arguments<'de, D>(
+    deserializer: D,
+) -> Result<Box<[Box<str>]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Box::<[Box<str>]>::deserialize(deserializer).and_then(|arguments| {
+        if arguments.is_empty() {
+            Err(DeserializeError::custom(
+                "command requires at least the program's name",
+            ))
+        } else {
+            Ok(arguments)
+        }
+    })
+}
+
+const fn default_timeout() -> Duration {
+    Command::DEFAULT_TIMEOUT
+}
+
+fn default_success_exit_codes() -> Box<[i32]> {
+    Box::new([0])
+}
@@ -1,8 +1,13 @@
-use std::time::Duration;
+use std::{net::SocketAddr, time::Duration};
 
-use serde::{Deserialize, Deserializer};
+use serde::{de::Error as _, Deserialize, Deserializer};
 
-use crate::{serde::deserialize_boxed_string, service::Configurations};
+use crate::{
+    command::Command,
+    reload::Reload,
+    serde::{deserialize_boxed_string, deserialize_seconds},
+    service::Configurations,
+};
 
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -15,12 +20,59 @@ pub(crate) struct Configuration {
     pub verbose_output: bool,
     #[serde(default, deserialize_with = "deserialize_boxed_string")]
     pub prepend: Box<str>,
+    #[serde(
+        default = "default_timeout",
+        deserialize_with = "deserialize_seconds",
+        rename = "timeout_seconds"
+    )]
+    pub timeout: Duration,
+    #[serde(
+        default = "default_connect_timeout",
+        deserialize_with = "deserialize_seconds",
+        rename = "connect_timeout_seconds"
+    )]
+    pub connect_timeout: Duration,
+    #[serde(default = "default_validation")]
+    pub validation: Option<Command>,
+    #[serde(default)]
+    pub reload: Reload,
+    #[serde(default)]
+    pub listen_address: Option<SocketAddr>,
+    #[serde(default, deserialize_with = "deserialize_loopback_address")]
+    pub admin_listen_address: Option<SocketAddr>,
     pub services: Configurations,
 }
 
-fn deserialize_seconds<'de, D>(deserialize: D) -> Result<Duration, D::Error>
+fn default_validation() -> Option<Command> {
+    Some(Command::new(
+        ["nginx", "-t", "-c", Command::CANDIDATE_PLACEHOLDER],
+        Command::DEFAULT_TIMEOUT,
+    ))
+}
+
+fn deserialize_loopback_address<'de, D>(
+    deserializer: D,
+) -> Result<Option<SocketAddr>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    u64::deserialize(deserialize).map(Duration::from_secs)
+    Option::<SocketAddr>::deserialize(deserializer).and_then(|address| {
+        match address {
+            Some(address) if !address.ip().is_loopback() => {
+                Err(D::Error::custom(format!(
+                    "admin API is not authenticated and can only listen on a \
+                    loopback address, while {address} is given",
+                )))
+            }
+            address => Ok(address),
+        }
+    })
+}
+
+const fn default_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+const fn default_connect_timeout() -> Duration {
+    Duration::from_secs(5)
 }
@@ -0,0 +1,336 @@
+use http_body_util::BodyExt as _;
+use hyper::Response as HttpResponse;
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE, TE},
+    Body, Client as ReqwestClient, Response as ReqwestResponse, Url,
+};
+
+use crate::{
+    http_client,
+    service::{Failure, FailureReason},
+};
+
+const GRPC_STATUS: &str = "grpc-status";
+
+const GRPC_MESSAGE: &str = "grpc-message";
+
+pub(crate) fn encode_request(message: &[u8]) -> Box<[u8]> {
+    let mut request = Vec::with_capacity(message.len() + 5);
+
+    request.push(0);
+
+    request.extend_from_slice(&(message.len() as u32).to_be_bytes());
+
+    request.extend_from_slice(message);
+
+    request.into_boxed_slice()
+}
+
+pub(crate) fn encode_string_field(
+    buffer: &mut Vec<u8>,
+    field_number: u32,
+    value: &str,
+) {
+    encode_varint(buffer, u64::from(field_number << 3 | 2));
+
+    encode_varint(buffer, value.len() as u64);
+
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+pub(crate) async fn unary(
+    client: &ReqwestClient,
+    url: Url,
+    request: &[u8],
+) -> Result<Box<[u8]>, Failure> {
+    let response = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/grpc")
+        .header(TE, "trailers")
+        .body(request.to_vec())
+        .send()
+        .await
+        .and_then(ReqwestResponse::error_for_status)
+        .map_err(http_client::failure)?;
+
+    let (parts, body) = HttpResponse::<Body>::from(response).into_parts();
+
+    let body = body.collect().await.map_err(http_client::failure)?;
+
+    check_status(if parts.headers.contains_key(GRPC_STATUS) {
+        Some(&parts.headers)
+    } else {
+        body.trailers()
+    })?;
+
+    decode_response(&body.to_bytes())
+}
+
+fn decode_response(body: &[u8]) -> Result<Box<[u8]>, Failure> {
+    let Some((&[compressed, length @ ..], message)) =
+        body.split_first_chunk::<5>()
+    else {
+        return Err(malformed("Response message is missing."));
+    };
+
+    if compressed != 0 {
+        return Err(malformed("Response message is compressed."));
+    }
+
+    message
+        .get(..u32::from_be_bytes(length) as usize)
+        .map(Into::into)
+        .ok_or_else(|| malformed("Response message is truncated."))
+}
+
+pub(crate) fn varint_field(
+    message: &[u8],
+    field_number: u32,
+) -> Result<Option<u64>, Failure> {
+    let mut message = message.iter().copied();
+
+    let mut field = None;
+
+    while let Some(key) = decode_varint(&mut message) {
+        match key & 0b111 {
+            0 => {
+                let value = decode_varint(&mut message)
+                    .ok_or_else(|| malformed("Field value is truncated."))?;
+
+                if key >> 3 == u64::from(field_number) {
+                    field = Some(value);
+                }
+            }
+            1 => {
+                message
+                    .nth(7)
+                    .ok_or_else(|| malformed("Field value is truncated."))?;
+            }
+            2 => {
+                let length = decode_varint(&mut message)
+                    .ok_or_else(|| malformed("Field length is truncated."))?;
+
+                if length != 0 {
+                    message.nth(length as usize - 1).ok_or_else(|| {
+                        malformed("Field value is truncated.")
+                    })?;
+                }
+            }
+            5 => {
+                message
+                    .nth(3)
+                    .ok_or_else(|| malformed("Field value is truncated."))?;
+            }
+            _ => return Err(malformed("Unsupported field wire type.")),
+        }
+    }
+
+    Ok(field)
+}
+
+fn check_status(metadata: Option<&HeaderMap>) -> Result<(), Failure> {
+    let Some((headers, status)) = metadata.and_then(|headers| {
+        headers.get(GRPC_STATUS).map(|status| (headers, status))
+    }) else {
+        return Err(malformed("Response is missing the gRPC status."));
+    };
+
+    if status == "0" {
+        Ok(())
+    } else {
+        Err(Failure::new(
+            FailureReason::Status,
+            format!(
+                "Server responded with gRPC status {}: {:?}",
+                String::from_utf8_lossy(status.as_bytes()),
+                headers
+                    .get(GRPC_MESSAGE)
+                    .map(|message| String::from_utf8_lossy(message.as_bytes()))
+                    .unwrap_or_default(),
+            ),
+        ))
+    }
+}
+
+fn malformed(details: &str) -> Failure {
+    Failure::new(
+        FailureReason::Response,
+        format!("Malformed gRPC response! {details}"),
+    )
+}
+
+fn encode_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7F) | 0x80);
+
+        value >>= 7;
+    }
+
+    buffer.push(value as u8);
+}
+
+fn decode_varint<I>(bytes: &mut I) -> Option<u64>
+where
+    I: Iterator<Item = u8>,
+{
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let byte = bytes.next()?;
+
+        value |= u64::from(byte & 0x7F) << shift;
+
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use crate::service::FailureReason;
+
+    use super::{
+        check_status, decode_response, decode_varint, encode_request,
+        encode_string_field, varint_field, GRPC_MESSAGE, GRPC_STATUS,
+    };
+
+    const SERVING: &[u8] = &[0x08, 0x01];
+
+    const NOT_SERVING: &[u8] = &[0x08, 0x02];
+
+    #[test]
+    fn encodes_request_frame() {
+        assert_eq!(&*encode_request(&[]), &[0, 0, 0, 0, 0]);
+
+        assert_eq!(&*encode_request(SERVING), &[0, 0, 0, 0, 2, 0x08, 0x01]);
+    }
+
+    #[test]
+    fn encodes_string_field() {
+        let mut buffer = vec![];
+
+        encode_string_field(&mut buffer, 1, "svc");
+
+        assert_eq!(buffer, [0x0A, 3, b's', b'v', b'c']);
+    }
+
+    #[test]
+    fn decodes_varints() {
+        assert_eq!(decode_varint(&mut [0x01].into_iter()), Some(1));
+
+        assert_eq!(decode_varint(&mut [0xAC, 0x02].into_iter()), Some(300));
+
+        assert_eq!(decode_varint(&mut [0x80].into_iter()), None);
+
+        assert_eq!(decode_varint(&mut [0xFF; 10].into_iter()), None);
+    }
+
+    #[test]
+    fn reads_health_check_response() {
+        assert_eq!(varint_field(SERVING, 1).unwrap(), Some(1));
+
+        assert_eq!(varint_field(NOT_SERVING, 1).unwrap(), Some(2));
+
+        assert_eq!(varint_field(&[], 1).unwrap(), None);
+    }
+
+    #[test]
+    fn skips_other_fields() {
+        let message = [
+            0x12, 2, b'a', b'b', 0x19, 1, 2, 3, 4, 5, 6, 7, 8, 0x25, 1, 2, 3,
+            4, 0x10, 0x07, 0x08, 0x01,
+        ];
+
+        assert_eq!(varint_field(&message, 1).unwrap(), Some(1));
+
+        assert_eq!(varint_field(&message, 2).unwrap(), Some(7));
+    }
+
+    #[test]
+    fn rejects_truncated_fields() {
+        for message in [
+            &[0x08][..],
+            &[0x08, 0x80],
+            &[0x12, 5, b'a'],
+            &[0x12],
+            &[0x19, 1, 2, 3],
+            &[0x25, 1],
+        ] {
+            assert_eq!(
+                varint_field(message, 1).unwrap_err().reason(),
+                FailureReason::Response,
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_wire_types() {
+        for message in [&[0x0B][..], &[0x0C], &[0x0E], &[0x0F]] {
+            assert_eq!(
+                varint_field(message, 1).unwrap_err().reason(),
+                FailureReason::Response,
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_response_frames() {
+        assert_eq!(
+            &*decode_response(&[0, 0, 0, 0, 2, 0x08, 0x01]).unwrap(),
+            SERVING,
+        );
+
+        assert!(decode_response(&[0, 0, 0, 0, 0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_response_frames() {
+        for body in [
+            &[][..],
+            &[0, 0, 0, 0],
+            &[0, 0, 0, 0, 3, 0x08, 0x01],
+            &[1, 0, 0, 0, 2, 0x08, 0x01],
+        ] {
+            assert_eq!(
+                decode_response(body).unwrap_err().reason(),
+                FailureReason::Response,
+            );
+        }
+    }
+
+    #[test]
+    fn checks_status() {
+        let mut metadata = HeaderMap::new();
+
+        assert_eq!(
+            check_status(None).unwrap_err().reason(),
+            FailureReason::Response,
+        );
+
+        assert_eq!(
+            check_status(Some(&metadata)).unwrap_err().reason(),
+            FailureReason::Response,
+        );
+
+        metadata.insert(GRPC_STATUS, HeaderValue::from_static("0"));
+
+        assert!(check_status(Some(&metadata)).is_ok());
+
+        metadata.insert(GRPC_STATUS, HeaderValue::from_static("14"));
+
+        metadata.insert(GRPC_MESSAGE, HeaderValue::from_static("unavailable"));
+
+        let failure = check_status(Some(&metadata)).unwrap_err();
+
+        assert_eq!(failure.reason(), FailureReason::Status);
+
+        assert!(failure.details().contains("14"));
+
+        assert!(failure.details().contains("unavailable"));
+    }
+}
@@ -1,20 +1,67 @@
-use std::time::Duration;
+use std::{error::Error as _, time::Duration};
 
 use anyhow::Result;
-use reqwest::Client as ReqwestClient;
+use reqwest::{Client as ReqwestClient, ClientBuilder, Error as ReqwestError};
 
-pub(crate) fn http_client() -> Result<ReqwestClient> {
-    ReqwestClient::builder()
+use crate::service::{Failure, FailureReason, Timeouts};
+
+pub(crate) fn http_client(timeouts: Timeouts) -> Result<ReqwestClient> {
+    builder(timeouts)
         .brotli(true)
         .deflate(true)
         .gzip(true)
         .http1_allow_obsolete_multiline_headers_in_responses(false)
         .http1_allow_spaces_after_header_name_in_responses(false)
         .http1_ignore_invalid_headers_in_responses(false)
-        .http2_keep_alive_interval(Some(Duration::from_secs(10)))
-        .http2_keep_alive_while_idle(true)
-        .https_only(false)
         .zstd(true)
         .build()
         .map_err(From::from)
 }
+
+pub(crate) fn grpc_client(timeouts: Timeouts) -> Result<ReqwestClient> {
+    builder(timeouts)
+        .http2_prior_knowledge()
+        .build()
+        .map_err(From::from)
+}
+
+fn builder(Timeouts { request, connect }: Timeouts) -> ClientBuilder {
+    ReqwestClient::builder()
+        .timeout(request)
+        .connect_timeout(connect)
+        .http2_keep_alive_interval(Some(Duration::from_secs(10)))
+        .http2_keep_alive_while_idle(true)
+        .https_only(false)
+}
+
+pub(crate) fn failure(error: ReqwestError) -> Failure {
+    let reason = if error.is_timeout() {
+        FailureReason::Timeout
+    } else if error.is_connect() {
+        FailureReason::Connect
+    } else if error.is_status() {
+        FailureReason::Status
+    } else if error.is_decode() || error.is_body() {
+        FailureReason::Response
+    } else {
+        FailureReason::Request
+    };
+
+    let mut details = error.to_string();
+
+    let mut source = error.source();
+
+    while let Some(error) = source {
+        let error_details = error.to_string();
+
+        if !details.ends_with(&error_details) {
+            details.push_str(": ");
+
+            details.push_str(&error_details);
+        }
+
+        source = error.source();
+    }
+
+    Failure::new(reason, details)
+}
@@ -0,0 +1,188 @@
+use std::{
+    convert::Infallible, future::Future, net::SocketAddr, sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{
+    body::Incoming, header::CONTENT_TYPE, server::conn::http1,
+    service::service_fn, Method, Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use tokio::{net::TcpListener, sync::watch, task::JoinHandle, time::sleep};
+use tracing::{debug, error, info};
+
+use crate::{metrics, snapshot::Snapshot, status};
+
+pub(crate) const PLAIN_TEXT: &str = "text/plain; charset=utf-8";
+
+const METRICS_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const JSON: &str = "application/json";
+
+pub(crate) type Snapshots = watch::Receiver<Arc<Snapshot>>;
+
+pub(crate) trait Handler: Clone + Send + Sync + 'static {
+    fn handle(
+        &self,
+        request: Request<Incoming>,
+    ) -> impl Future<Output = Response<Full<Bytes>>> + Send;
+}
+
+#[derive(Default)]
+pub(crate) struct Server {
+    listening: Option<(SocketAddr, JoinHandle<()>)>,
+}
+
+impl Server {
+    pub async fn update<H>(
+        &mut self,
+        address: Option<SocketAddr>,
+        handler: &H,
+    ) -> Result<()>
+    where
+        H: Handler,
+    {
+        if self.listening.as_ref().map(|&(address, _)| address) == address {
+            return Ok(());
+        }
+
+        if let Some((address, task)) = self.listening.take() {
+            task.abort();
+
+            info!("Stopped listening for HTTP requests on {address}.");
+        }
+
+        let Some(address) = address else {
+            return Ok(());
+        };
+
+        let listener = TcpListener::bind(address).await.with_context(|| {
+            format!("Failed to listen for HTTP requests on {address}!")
+        })?;
+
+        info!("Listening for HTTP requests on {address}.");
+
+        self.listening =
+            Some((address, tokio::spawn(serve(listener, handler.clone()))));
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Monitoring {
+    snapshots: Snapshots,
+}
+
+impl Monitoring {
+    #[inline]
+    pub const fn new(snapshots: Snapshots) -> Self {
+        Self { snapshots }
+    }
+}
+
+impl Handler for Monitoring {
+    async fn handle(
+        &self,
+        request: Request<Incoming>,
+    ) -> Response<Full<Bytes>> {
+        match (request.method(), request.uri().path()) {
+            (&Method::GET, "/metrics") => {
+                let snapshot = Arc::clone(&self.snapshots.borrow());
+
+                response(
+                    StatusCode::OK,
+                    METRICS_TEXT,
+                    metrics::render(&snapshot),
+                )
+            }
+            (&Method::GET, "/status") => {
+                let snapshot = Arc::clone(&self.snapshots.borrow());
+
+                match status::render(&snapshot) {
+                    Ok(status) => response(StatusCode::OK, JSON, status),
+                    Err(error) => {
+                        error!(
+                            ?error,
+                            "Failed to render status! Cause: {error}",
+                        );
+
+                        response(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            PLAIN_TEXT,
+                            format!(
+                                "Failed to render status! Cause: {error}\n"
+                            ),
+                        )
+                    }
+                }
+            }
+            (_, "/metrics" | "/status") => response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                PLAIN_TEXT,
+                "Method not allowed.\n",
+            ),
+            _ => response(StatusCode::NOT_FOUND, PLAIN_TEXT, "Not found.\n"),
+        }
+    }
+}
+
+pub(crate) fn response<T>(
+    status: StatusCode,
+    content_type: &'static str,
+    body: T,
+) -> Response<Full<Bytes>>
+where
+    T: Into<Bytes>,
+{
+    let mut response = Response::new(Full::new(body.into()));
+
+    *response.status_mut() = status;
+
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, content_type.parse().expect("Valid header!"));
+
+    response
+}
+
+async fn serve<H>(listener: TcpListener, handler: H)
+where
+    H: Handler,
+{
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                error!(
+                    ?error,
+                    "Failed to accept HTTP connection! Cause: {error}"
+                );
+
+                sleep(Duration::from_millis(100)).await;
+
+                continue;
+            }
+        };
+
+        let handler = handler.clone();
+
+        tokio::spawn(async move {
+            let service = service_fn(|request| {
+                let handler = handler.clone();
+
+                async move { Ok::<_, Infallible>(handler.handle(request).await) }
+            });
+
+            if let Err(error) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!(?error, "HTTP connection failed! Cause: {error}");
+            }
+        });
+    }
+}
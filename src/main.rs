@@ -1,59 +1,110 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, process::ExitCode, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
 use tokio::{
     select,
     signal::unix::{signal, SignalKind},
+    sync::{mpsc, watch},
     time::{interval, MissedTickBehavior},
 };
-use tracing::{info, Level};
+use tracing::{error, info, Level};
+use tracing_subscriber::fmt::MakeWriter;
 
-use crate::state::State;
+use crate::{
+    admin::{Admin, OverrideOutcome, OverrideRequest},
+    http_server::{Monitoring, Server},
+    snapshot::Snapshot,
+    state::{State, WriteOutOutcome},
+};
 
+mod admin;
+mod check;
+mod command;
 mod configuration;
+mod grpc;
 mod http_client;
+mod http_server;
+mod metrics;
+mod output_file;
+mod reload;
 mod serde;
 mod service;
 mod services;
+mod snapshot;
 mod state;
+mod status;
 mod url;
 mod writer;
 
 #[derive(Parser)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Subcommand>,
     #[clap(
         short = 'c',
         long,
+        required = true,
         visible_alias = "static-config",
         visible_alias = "static-configuration"
     )]
-    r#static: PathBuf,
+    r#static: Option<PathBuf>,
     #[clap(
         short,
         long,
+        required = true,
         visible_alias = "services-config",
         visible_alias = "services-configuration"
     )]
-    services: PathBuf,
+    services: Option<PathBuf>,
     #[clap(
         short,
         long,
+        required = true,
         visible_alias = "output-config",
         visible_alias = "output-configuration"
     )]
-    output: PathBuf,
+    output: Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand)]
+enum Subcommand {
+    Check {
+        #[clap(
+            short,
+            long,
+            visible_alias = "services-config",
+            visible_alias = "services-configuration"
+        )]
+        services: PathBuf,
+        #[clap(long)]
+        json: bool,
+    },
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     let Args {
+        command,
         r#static,
         services,
         output,
     } = Parser::parse();
 
-    initialize_logging();
+    if let Some(Subcommand::Check { services, json }) = command {
+        initialize_logging(std::io::stderr);
+
+        return check::run(&services, json).await;
+    }
+
+    let (Some(r#static), Some(services), Some(output)) =
+        (r#static, services, output)
+    else {
+        unreachable!("Arguments should be required by the parser!");
+    };
+
+    initialize_logging(std::io::stdout);
 
     let mut state = State::load(&r#static, &services).await?;
 
@@ -65,6 +116,27 @@ async fn main() -> Result<()> {
         interval
     };
 
+    let (snapshot_sender, snapshots) =
+        watch::channel(Arc::new(Snapshot::default()));
+
+    let monitoring = Monitoring::new(snapshots);
+
+    let mut monitoring_server = Server::default();
+
+    monitoring_server
+        .update(state.listen_address(), &monitoring)
+        .await?;
+
+    let (override_sender, mut override_requests) = mpsc::channel(16);
+
+    let admin = Admin::new(override_sender);
+
+    let mut admin_server = Server::default();
+
+    admin_server
+        .update(state.admin_listen_address(), &admin)
+        .await?;
+
     let mut signal = signal(SignalKind::hangup())?;
 
     let mut forced = true;
@@ -76,23 +148,100 @@ async fn main() -> Result<()> {
             _ = signal.recv() => {
                 info!("\"SIGHUP\" received. Configuration reload requested.");
 
-                state = State::load(
-                    &r#static,
-                    &services,
-                )
-                .await?;
+                if state.reload(&r#static, &services).await {
+                    forced = true;
+
+                    if let Err(error) = monitoring_server
+                        .update(state.listen_address(), &monitoring)
+                        .await
+                    {
+                        error!(
+                            ?error,
+                            "Failed to update HTTP listener! Cause: {error:#}",
+                        );
+                    }
 
-                forced = true;
+                    if let Err(error) = admin_server
+                        .update(state.admin_listen_address(), &admin)
+                        .await
+                    {
+                        error!(
+                            ?error,
+                            "Failed to update admin HTTP listener! Cause: \
+                            {error:#}",
+                        );
+                    }
+                }
+            },
+            Some(OverrideRequest {
+                service_name,
+                instance_name,
+                manual_override,
+                respond,
+            }) = override_requests.recv() => {
+                let found = state.set_manual_override(
+                    &service_name,
+                    &instance_name,
+                    manual_override,
+                );
+
+                if !found {
+                    _ = respond.send(OverrideOutcome::NotFound);
+
+                    continue;
+                }
+
+                let outcome = match state
+                    .write_out_configuration(&output)
+                    .await
+                {
+                    Ok(WriteOutOutcome::Written) => OverrideOutcome::Applied,
+                    Ok(WriteOutOutcome::ValidationRejected) => {
+                        OverrideOutcome::NotWrittenOut(
+                            "Candidate output configuration failed \
+                            validation."
+                                .into(),
+                        )
+                    }
+                    Ok(WriteOutOutcome::ReloadFailed) => {
+                        OverrideOutcome::NotWrittenOut(
+                            "Failed to reload NGINX.".into(),
+                        )
+                    }
+                    Err(error) => {
+                        error!(
+                            ?error,
+                            "Failed to write out output configuration after \
+                            manual override! Retrying on the next cycle. \
+                            Cause: {error:#}",
+                        );
+
+                        OverrideOutcome::NotWrittenOut(
+                            format!("{error:#}").into_boxed_str(),
+                        )
+                    }
+                };
+
+                _ = respond.send(outcome);
+
+                snapshot_sender.send_replace(Arc::new(state.snapshot().await));
+
+                continue;
             },
         }
 
         state.output_configuration(&output, forced).await?;
 
+        snapshot_sender.send_replace(Arc::new(state.snapshot().await));
+
         forced = false;
     }
 }
 
-fn initialize_logging() {
+fn initialize_logging<W>(writer: W)
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
     tracing_subscriber::fmt::fmt()
         .compact()
         .with_ansi(true)
@@ -104,7 +253,7 @@ fn initialize_logging() {
         .with_max_level(
             if cfg!(debug_assertions)
                 || std::env::var_os("DEBUG_LOG")
-                    .map_or(false, |value| value == "1")
+                    .is_some_and(|value| value == "1")
             {
                 Level::DEBUG
             } else {
@@ -112,6 +261,6 @@ fn initialize_logging() {
             },
         )
         .with_thread_names(false)
-        .with_writer(std::io::stdout)
+        .with_writer(writer)
         .init()
 }
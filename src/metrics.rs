@@ -0,0 +1,228 @@
+use std::{
+    fmt::{Result as FmtResult, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    service::Status,
+    snapshot::{InstanceSnapshot, ReloadCounters, Snapshot},
+};
+
+const PREFIX: &str = "nginx_healthchecker";
+
+pub(crate) fn render(snapshot: &Snapshot) -> String {
+    let mut output = String::new();
+
+    write_metrics(&mut output, snapshot)
+        .expect("Writing to a string should be infallible!");
+
+    output
+}
+
+fn write_metrics(output: &mut String, snapshot: &Snapshot) -> FmtResult {
+    header(
+        output,
+        "instance_up",
+        "gauge",
+        "Whether the instance is considered healthy.",
+    )?;
+
+    for_each_instance(snapshot, |service_name, instance| {
+        writeln!(
+            output,
+            "{PREFIX}_instance_up{{{}}} {}",
+            labels(service_name, instance),
+            u8::from(matches!(instance.status, Status::Enabled)),
+        )
+    })?;
+
+    header(
+        output,
+        "check_duration_seconds",
+        "histogram",
+        "Duration of healthchecks.",
+    )?;
+
+    for_each_instance(snapshot, |service_name, instance| {
+        let labels = labels(service_name, instance);
+
+        let latency = instance.statistics.latency();
+
+        for (bound, count) in latency.buckets() {
+            writeln!(
+                output,
+                "{PREFIX}_check_duration_seconds_bucket{{{labels},le=\"{bound}\"}} \
+                {count}",
+            )?;
+        }
+
+        writeln!(
+            output,
+            "{PREFIX}_check_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+            latency.count(),
+        )?;
+
+        writeln!(
+            output,
+            "{PREFIX}_check_duration_seconds_sum{{{labels}}} {}",
+            latency.sum(),
+        )?;
+
+        writeln!(
+            output,
+            "{PREFIX}_check_duration_seconds_count{{{labels}}} {}",
+            latency.count(),
+        )
+    })?;
+
+    header(
+        output,
+        "check_failures_total",
+        "counter",
+        "Number of failed healthchecks, by failure reason.",
+    )?;
+
+    for_each_instance(snapshot, |service_name, instance| {
+        let labels = labels(service_name, instance);
+
+        instance
+            .statistics
+            .failures()
+            .iter()
+            .try_for_each(|(reason, count)| {
+                writeln!(
+                    output,
+                    "{PREFIX}_check_failures_total{{{labels},reason=\"{reason}\"}} \
+                    {count}",
+                )
+            })
+    })?;
+
+    header(
+        output,
+        "node_block_height",
+        "gauge",
+        "Latest block height observed on the node.",
+    )?;
+
+    for_each_instance(snapshot, |service_name, instance| {
+        instance.block_height.map_or(Ok(()), |block_height| {
+            writeln!(
+                output,
+                "{PREFIX}_node_block_height{{{}}} {block_height}",
+                labels(service_name, instance),
+            )
+        })
+    })?;
+
+    write_reloads(
+        output,
+        "configuration_reloads",
+        "configuration",
+        &snapshot.configuration_reloads,
+    )?;
+
+    write_reloads(output, "nginx_reloads", "NGINX", &snapshot.nginx_reloads)
+}
+
+fn write_reloads(
+    output: &mut String,
+    name: &str,
+    subject: &str,
+    counters: &ReloadCounters,
+) -> FmtResult {
+    header(
+        output,
+        &format!("{name}_total"),
+        "counter",
+        &format!("Number of {subject} reloads, by result."),
+    )?;
+
+    writeln!(
+        output,
+        "{PREFIX}_{name}_total{{result=\"success\"}} {}",
+        counters.successful,
+    )?;
+
+    writeln!(
+        output,
+        "{PREFIX}_{name}_total{{result=\"failure\"}} {}",
+        counters.failed,
+    )?;
+
+    if let Some(last_succeeded) = counters.last_succeeded {
+        header(
+            output,
+            &format!("{name}_last_success"),
+            "gauge",
+            &format!("Whether the last {subject} reload succeeded."),
+        )?;
+
+        writeln!(
+            output,
+            "{PREFIX}_{name}_last_success {}",
+            u8::from(last_succeeded),
+        )?;
+    }
+
+    if let Some(last_success_at) = counters.last_success_at {
+        header(
+            output,
+            &format!("{name}_last_success_timestamp_seconds"),
+            "gauge",
+            &format!("Time of the last successful {subject} reload."),
+        )?;
+
+        writeln!(
+            output,
+            "{PREFIX}_{name}_last_success_timestamp_seconds {}",
+            unix_seconds(last_success_at),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn header(
+    output: &mut String,
+    name: &str,
+    r#type: &str,
+    help: &str,
+) -> FmtResult {
+    writeln!(output, "# HELP {PREFIX}_{name} {help}")?;
+
+    writeln!(output, "# TYPE {PREFIX}_{name} {type}")
+}
+
+fn for_each_instance<F>(snapshot: &Snapshot, mut f: F) -> FmtResult
+where
+    F: FnMut(&str, &InstanceSnapshot) -> FmtResult,
+{
+    snapshot.services.iter().try_for_each(|service| {
+        service
+            .instances
+            .iter()
+            .try_for_each(|instance| f(&service.service_name, instance))
+    })
+}
+
+fn labels(service_name: &str, instance: &InstanceSnapshot) -> String {
+    format!(
+        "service=\"{}\",instance=\"{}\"",
+        escape(service_name),
+        escape(&instance.instance_name),
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn unix_seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
@@ -0,0 +1,168 @@
+use std::{
+    ffi::OsString,
+    io::ErrorKind,
+    os::unix::fs::{chown, MetadataExt as _},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result};
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::AsyncWriteExt as _,
+};
+use tracing::warn;
+
+pub(crate) struct Candidate {
+    path: PathBuf,
+    file: File,
+}
+
+impl Candidate {
+    pub async fn create(output_configuration: &Path) -> Result<Self> {
+        let path = {
+            let mut path = OsString::from(output_configuration.as_os_str());
+
+            path.push(".candidate");
+
+            PathBuf::from(path)
+        };
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .await
+            .context("Failed to create candidate output configuration!")?;
+
+        let candidate = Self { path, file };
+
+        match candidate.inherit_metadata(output_configuration).await {
+            Ok(()) => Ok(candidate),
+            Err(error) => {
+                candidate.discard().await;
+
+                Err(error)
+            }
+        }
+    }
+
+    async fn inherit_metadata(
+        &self,
+        output_configuration: &Path,
+    ) -> Result<()> {
+        let metadata = match fs::metadata(output_configuration).await {
+            Ok(metadata) => metadata,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Ok(());
+            }
+            Err(error) => {
+                return Err(error).context(
+                    "Failed to read metadata of output configuration!",
+                );
+            }
+        };
+
+        self.file
+            .set_permissions(metadata.permissions())
+            .await
+            .context(
+                "Failed to apply output configuration's permissions to the \
+                candidate!",
+            )?;
+
+        let candidate_metadata = self.file.metadata().await.context(
+            "Failed to read metadata of candidate output configuration!",
+        )?;
+
+        if (candidate_metadata.uid(), candidate_metadata.gid())
+            != (metadata.uid(), metadata.gid())
+        {
+            if let Err(error) =
+                chown(&self.path, Some(metadata.uid()), Some(metadata.gid()))
+            {
+                warn!(
+                    ?error,
+                    "Failed to apply output configuration's ownership to the \
+                    candidate! Cause: {error}",
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn file(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    pub async fn sync(&mut self) -> Result<()> {
+        self.file.flush().await?;
+
+        self.file
+            .sync_all()
+            .await
+            .context("Failed to synchronize candidate output configuration!")
+    }
+
+    pub async fn commit(self, output_configuration: &Path) -> Result<()> {
+        let Self { path, file } = self;
+
+        drop(file);
+
+        if let Err(error) = fs::rename(&path, output_configuration).await {
+            Self::remove(&path).await;
+
+            return Err(error).context(
+                "Failed to replace output configuration with the candidate!",
+            );
+        }
+
+        let directory = match output_configuration.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory,
+            _ => Path::new("."),
+        };
+
+        if let Err(error) = Self::sync_directory(directory).await {
+            warn!(
+                ?error,
+                "Failed to synchronize output configuration's directory! \
+                Cause: {error}",
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn sync_directory(directory: &Path) -> Result<()> {
+        File::open(directory)
+            .await?
+            .sync_all()
+            .await
+            .map_err(From::from)
+    }
+
+    pub async fn discard(self) {
+        let Self { path, file } = self;
+
+        drop(file);
+
+        Self::remove(&path).await;
+    }
+
+    async fn remove(path: &Path) {
+        if let Err(error) = fs::remove_file(path).await {
+            warn!(
+                ?error,
+                "Failed to remove candidate output configuration! Cause: \
+                {error}",
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::{io::Error as IoError, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde::Deserialize;
+use tokio::{fs, time::timeout};
+
+use crate::{command::Command, serde::deserialize_seconds};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "type")]
+pub(crate) enum Reload {
+    Command(Command),
+    PidfileSignal {
+        pidfile: PathBuf,
+        #[serde(
+            default = "default_pidfile_timeout",
+            deserialize_with = "deserialize_seconds",
+            rename = "timeout_seconds"
+        )]
+        timeout: Duration,
+    },
+    None,
+}
+
+impl Default for Reload {
+    fn default() -> Self {
+        Self::Command(Command::new(
+            ["systemctl", "reload", "nginx.service"],
+            Command::DEFAULT_TIMEOUT,
+        ))
+    }
+}
+
+impl Reload {
+    pub async fn run(&self) -> Result<()> {
+        match self {
+            Self::Command(command) => {
+                let output = command.run(&[]).await?;
+
+                if command.succeeded(output.status) {
+                    Ok(())
+                } else {
+                    Err(anyhow!(
+                        "Reload command exited with {status}! Standard \
+                        error: {stderr:?}",
+                        status = output.status,
+                        stderr = String::from_utf8_lossy(&output.stderr).trim(),
+                    ))
+                }
+            }
+            Self::PidfileSignal {
+                pidfile,
+                timeout: pidfile_timeout,
+            } => timeout(*pidfile_timeout, Self::signal_pidfile(pidfile))
+                .await
+                .map_err(|_| {
+                    anyhow!(
+                        "Signalling process from pidfile {pidfile:?} did not \
+                        complete within {} seconds!",
+                        pidfile_timeout.as_secs(),
+                    )
+                })?,
+            Self::None => Ok(()),
+        }
+    }
+
+    async fn signal_pidfile(pidfile: &PathBuf) -> Result<()> {
+        let pid: libc::pid_t = fs::read_to_string(pidfile)
+            .await
+            .with_context(|| format!("Failed to read pidfile {pidfile:?}!"))?
+            .trim()
+            .parse()
+            .with_context(|| format!("Failed to parse pidfile {pidfile:?}!"))?;
+
+        if pid <= 0 {
+            bail!("Pidfile {pidfile:?} contains an invalid process ID!");
+        }
+
+        if unsafe { libc::kill(pid, libc::SIGHUP) } == 0 {
+            Ok(())
+        } else {
+            Err(IoError::last_os_error()).with_context(|| {
+                format!("Failed to send \"SIGHUP\" to process {pid}!")
+            })
+        }
+    }
+}
+
+const fn default_pidfile_timeout() -> Duration {
+    Duration::from_secs(5)
+}
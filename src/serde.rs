@@ -1,4 +1,7 @@
-use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+use regex::Regex;
+use serde::{de::Error as _, Deserialize, Deserializer};
 
 pub(crate) fn deserialize_boxed_string<'de, D>(
     deserialize: D,
@@ -8,3 +11,35 @@ where
 {
     String::deserialize(deserialize).map(|value| value.trim().into())
 }
+
+pub(crate) fn deserialize_seconds<'de, D>(
+    deserialize: D,
+) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    u64::deserialize(deserialize).map(Duration::from_secs)
+}
+
+pub(crate) fn deserialize_optional_seconds<'de, D>(
+    deserialize: D,
+) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<u64>::deserialize(deserialize)
+        .map(|value| value.map(Duration::from_secs))
+}
+
+pub(crate) fn deserialize_optional_regex<'de, D>(
+    deserialize: D,
+) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserialize).and_then(|value| {
+        value
+            .map(|value| Regex::new(&value).map_err(D::Error::custom))
+            .transpose()
+    })
+}
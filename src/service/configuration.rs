@@ -1,10 +1,12 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap, num::NonZeroU32, path::PathBuf, time::Duration,
+};
 
 use serde::Deserialize;
 
 use crate::{
-    serde::deserialize_boxed_string,
-    services::{generic_200_ok, node},
+    serde::{deserialize_boxed_string, deserialize_optional_seconds},
+    services::{generic_200_ok, grpc_health, node, tcp_connect},
 };
 
 pub(crate) type Configurations = BTreeMap<Box<str>, Configuration>;
@@ -13,7 +15,7 @@ macro_rules! define_configuration {
     ($visibility:vis enum $enum:ident {
         $(
             $(#[$($attributes:tt)+])*
-            $variant:ident < $configuration:ty $(,)? >
+            $variant:ident < $configuration:ty $(, $service:ty)? $(,)? >
         ),+ $(,)?
     }) => {
         #[derive(Deserialize)]
@@ -24,6 +26,12 @@ macro_rules! define_configuration {
                 $variant {
                     #[serde(default, deserialize_with = "deserialize_boxed_string")]
                     prepend: Box<str>,
+                    #[serde(flatten)]
+                    settings: Settings,
+                    $(
+                        #[serde(flatten)]
+                        service: $service,
+                    )?
                     instances: Instances<$configuration>,
                 },
             )+
@@ -35,8 +43,79 @@ define_configuration! {
     pub(crate) enum Configuration {
         #[serde(rename = "generic_200_ok")]
         Generic200Ok<generic_200_ok::StorageConfiguration>,
-        Node<node::StorageConfiguration>,
+        Node<node::StorageConfiguration, node::ServiceConfiguration>,
+        GrpcHealth<grpc_health::StorageConfiguration>,
+        TcpConnect<tcp_connect::StorageConfiguration>,
     }
 }
 
-pub(crate) type Instances<C> = BTreeMap<Box<str>, C>;
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct Settings {
+    #[serde(default)]
+    pub rise: Option<NonZeroU32>,
+    #[serde(default)]
+    pub fall: Option<NonZeroU32>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_seconds",
+        rename = "timeout_seconds"
+    )]
+    pub timeout: Option<Duration>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_seconds",
+        rename = "connect_timeout_seconds"
+    )]
+    pub connect_timeout: Option<Duration>,
+    #[serde(default)]
+    pub all_down_fallback: AllDownFallback,
+    #[serde(default)]
+    pub output_mode: OutputMode,
+    #[serde(default)]
+    pub maintenance_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) enum AllDownFallback {
+    LastKnownGood,
+    #[default]
+    AllInstances,
+    Sentinel(#[serde(deserialize_with = "deserialize_boxed_string")] Box<str>),
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) enum OutputMode {
+    #[default]
+    OmitUnhealthy,
+    MarkDown,
+}
+
+pub(crate) type Instances<C> = BTreeMap<Box<str>, Instance<C>>;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct Instance<C> {
+    #[serde(default)]
+    pub rise: Option<NonZeroU32>,
+    #[serde(default)]
+    pub fall: Option<NonZeroU32>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_seconds",
+        rename = "timeout_seconds"
+    )]
+    pub timeout: Option<Duration>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_seconds",
+        rename = "connect_timeout_seconds"
+    )]
+    pub connect_timeout: Option<Duration>,
+    #[serde(default)]
+    pub maintenance_file: Option<PathBuf>,
+    #[serde(flatten)]
+    pub configuration: C,
+}
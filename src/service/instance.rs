@@ -1,40 +1,182 @@
-use std::ops::BitAnd;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    future::Future,
+    num::NonZeroU32,
+    ops::BitAnd,
+    path::Path,
+    time::{Duration, Instant, SystemTime},
+};
 
-use crate::state::Epoch;
+use tracing::info;
+
+use crate::{snapshot::InstanceSnapshot, state::Epoch};
+
+use super::{
+    maintenance::Maintenance,
+    manual_override::{ManualOverride, OverrideMode},
+    statistics::Statistics,
+};
 
 #[derive(Clone)]
 pub(crate) struct Instance<C, S> {
     instance_name: Box<str>,
     status: Status,
+    last_failure: Option<Failure>,
+    thresholds: Thresholds,
+    counters: Counters,
+    statistics: Statistics,
+    manual_override: Option<ManualOverride>,
+    maintenance: Maintenance,
     configuration: C,
     state: S,
 }
 
 impl<C, S> Instance<C, S> {
     #[inline]
-    pub const fn new(
+    pub fn new(
         instance_name: Box<str>,
-        status: Status,
+        result: Result<(), Failure>,
+        thresholds: Thresholds,
         configuration: C,
         state: S,
     ) -> Self {
         Self {
             instance_name,
-            status,
+            status: result.is_ok().into(),
+            last_failure: result.err(),
+            thresholds,
+            counters: Counters::new(),
+            statistics: Statistics::default(),
+            manual_override: None,
+            maintenance: Maintenance::default(),
             configuration,
             state,
         }
     }
 
     #[inline]
-    pub const fn enabled(&self) -> Status {
-        self.status
+    #[must_use]
+    pub fn with_maintenance_files(mut self, files: Box<[Box<Path>]>) -> Self {
+        self.maintenance = Maintenance::new(files);
+
+        self
+    }
+
+    #[inline]
+    pub fn enabled(&self) -> Status {
+        match self.manual_override {
+            Some(ManualOverride {
+                mode: OverrideMode::ForceUp,
+                ..
+            }) => Status::Enabled,
+            Some(ManualOverride {
+                mode: OverrideMode::Drain | OverrideMode::ForceDown,
+                ..
+            }) => Status::Disabled,
+            None if self.maintenance.active_file().is_some() => {
+                Status::Disabled
+            }
+            None => self.status,
+        }
+    }
+
+    #[inline]
+    pub fn maintenance_file(&self) -> Option<&Path> {
+        self.maintenance.active_file()
+    }
+
+    #[inline]
+    pub const fn override_mode(&self) -> Option<OverrideMode> {
+        match self.manual_override {
+            Some(ManualOverride { mode, .. }) => Some(mode),
+            None => None,
+        }
+    }
+
+    #[inline]
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
+
+    pub fn set_manual_override(
+        &mut self,
+        service_name: &str,
+        manual_override: Option<ManualOverride>,
+    ) {
+        let instance_name = &self.instance_name;
+
+        match manual_override {
+            Some(ManualOverride {
+                mode,
+                expires_at: Some(expires_at),
+            }) => info!(
+                "[{service_name:?}; {instance_name:?}] manually overridden \
+                with {mode} for {} seconds.",
+                expires_at
+                    .duration_since(SystemTime::now())
+                    .unwrap_or_default()
+                    .as_secs(),
+            ),
+            Some(ManualOverride {
+                mode,
+                expires_at: None,
+            }) => info!(
+                "[{service_name:?}; {instance_name:?}] manually overridden \
+                with {mode}.",
+            ),
+            None => info!(
+                "[{service_name:?}; {instance_name:?}] manual override \
+                cleared.",
+            ),
+        }
+
+        self.manual_override = manual_override;
+    }
+
+    pub async fn refresh_overrides(
+        &mut self,
+        service_name: &str,
+        now: SystemTime,
+    ) -> StateChange {
+        let expired = match self.manual_override {
+            Some(manual_override) if manual_override.expired(now) => {
+                info!(
+                    "[{service_name:?}; {:?}] manual override with {} \
+                    expired.",
+                    self.instance_name, manual_override.mode,
+                );
+
+                self.manual_override = None;
+
+                StateChange::Changed
+            }
+            _ => StateChange::Unchanged,
+        };
+
+        expired
+            & self
+                .maintenance
+                .refresh(InstanceName {
+                    service_name,
+                    instance_name: &self.instance_name,
+                })
+                .await
+    }
+
+    #[inline]
+    pub const fn last_failure(&self) -> Option<&Failure> {
+        self.last_failure.as_ref()
     }
 
     #[inline]
     pub const fn configuration(&self) -> &C {
         &self.configuration
     }
+
+    #[inline]
+    pub const fn state(&self) -> &S {
+        &self.state
+    }
 }
 
 impl<C, S> Instance<C, S>
@@ -46,7 +188,9 @@ where
         epoch: Epoch,
         output_verbosity: OutputVerbosity<ServiceName<'_>>,
     ) -> StateChange {
-        let enabled = self
+        let started_at = Instant::now();
+
+        let result = self
             .state
             .healthcheck(
                 epoch,
@@ -59,12 +203,158 @@ where
             )
             .await;
 
+        self.statistics.register(
+            self.state
+                .check_duration()
+                .unwrap_or_else(|| started_at.elapsed()),
+            result.as_ref().err().map(Failure::reason),
+        );
+
+        let enabled = match result {
+            Err(failure) => {
+                self.last_failure = Some(failure);
+
+                self.counters.register_failure();
+
+                if self.counters.consecutive_failures
+                    >= self.thresholds.fall.get()
+                {
+                    Status::Disabled
+                } else {
+                    self.status
+                }
+            }
+            Ok(()) => {
+                self.counters.register_success();
+
+                if self.counters.consecutive_successes
+                    >= self.thresholds.rise.get()
+                {
+                    Status::Enabled
+                } else {
+                    self.status
+                }
+            }
+        };
+
         if std::mem::replace(&mut self.status, enabled) != enabled {
             StateChange::Changed
         } else {
             StateChange::Unchanged
         }
     }
+
+    pub async fn snapshot(&self) -> InstanceSnapshot {
+        InstanceSnapshot {
+            instance_name: self.instance_name.clone(),
+            status: self.enabled(),
+            manual_override: self.manual_override,
+            maintenance_file: self.maintenance.active_file().map(Into::into),
+            last_failure: self.last_failure.clone(),
+            consecutive_successes: self.counters.consecutive_successes,
+            consecutive_failures: self.counters.consecutive_failures,
+            statistics: self.statistics.clone(),
+            block_height: self.state.block_height().await,
+        }
+    }
+
+    pub async fn carry_over(&mut self, previous: &Self) -> bool {
+        if self.instance_name != previous.instance_name {
+            return false;
+        }
+
+        self.manual_override = previous.manual_override;
+
+        self.maintenance.carry_over(&previous.maintenance);
+
+        if self.state.same_target(&previous.state) {
+            self.status = previous.status;
+
+            self.last_failure.clone_from(&previous.last_failure);
+
+            self.counters = previous.counters;
+
+            self.statistics.clone_from(&previous.statistics);
+
+            self.state.carry_over(&previous.state).await;
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct InstanceSettings {
+    pub thresholds: Thresholds,
+    pub timeouts: Timeouts,
+    pub mode: CheckMode,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Thresholds {
+    pub rise: NonZeroU32,
+    pub fall: NonZeroU32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CheckMode {
+    Continuous,
+    OneShot,
+}
+
+impl CheckMode {
+    pub async fn initial_result<F>(self, healthcheck: F) -> Result<(), Failure>
+    where
+        F: Future<Output = Result<(), Failure>>,
+    {
+        match self {
+            Self::Continuous => healthcheck.await,
+            Self::OneShot => Err(Self::not_checked_yet()),
+        }
+    }
+
+    pub fn not_checked_yet() -> Failure {
+        Failure::new(FailureReason::Request, "Healthcheck has not run yet.")
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Timeouts {
+    pub request: Duration,
+    pub connect: Duration,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    consecutive_successes: u32,
+    consecutive_failures: u32,
+}
+
+impl Counters {
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            consecutive_successes: 0,
+            consecutive_failures: 0,
+        }
+    }
+
+    #[inline]
+    fn register_success(&mut self) {
+        self.consecutive_successes =
+            self.consecutive_successes.saturating_add(1);
+
+        self.consecutive_failures = 0;
+    }
+
+    #[inline]
+    fn register_failure(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+
+        self.consecutive_successes = 0;
+    }
 }
 
 pub(crate) trait Configuration {
@@ -76,7 +366,19 @@ pub(crate) trait Healthcheck {
         &mut self,
         epoch: Epoch,
         output_verbosity: OutputVerbosity<InstanceName<'_>>,
-    ) -> Status;
+    ) -> Result<(), Failure>;
+
+    fn same_target(&self, previous: &Self) -> bool;
+
+    async fn carry_over(&mut self, previous: &Self);
+
+    fn check_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    async fn block_height(&self) -> Option<u64> {
+        None
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -98,6 +400,24 @@ impl<T> OutputVerbosity<T> {
     }
 }
 
+impl OutputVerbosity<InstanceName<'_>> {
+    pub fn report(self, result: &Result<(), Failure>) {
+        if let Self::Verbose(InstanceName {
+            service_name,
+            instance_name,
+        }) = self
+        {
+            match result {
+                Ok(()) => info!("[{service_name:?}; {instance_name:?}] is UP."),
+                Err(failure) => info!(
+                    "[{service_name:?}; {instance_name:?}] is DOWN. Cause: \
+                    {failure}"
+                ),
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct ServiceName<'r> {
     pub service_name: &'r str,
@@ -115,6 +435,15 @@ pub(crate) enum Status {
     Enabled,
 }
 
+impl Status {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::Enabled => "enabled",
+        }
+    }
+}
+
 impl From<bool> for Status {
     #[inline]
     fn from(value: bool) -> Self {
@@ -126,6 +455,81 @@ impl From<bool> for Status {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum FailureReason {
+    Timeout,
+    Connect,
+    Request,
+    Status,
+    Response,
+    NotServing,
+    CatchingUp,
+    Stalled,
+    BlockLag,
+    StaleBlock,
+    IdentityMismatch,
+}
+
+impl FailureReason {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Connect => "connect",
+            Self::Request => "request",
+            Self::Status => "status",
+            Self::Response => "response",
+            Self::NotServing => "not_serving",
+            Self::CatchingUp => "catching_up",
+            Self::Stalled => "stalled",
+            Self::BlockLag => "block_lag",
+            Self::StaleBlock => "stale_block",
+            Self::IdentityMismatch => "identity_mismatch",
+        }
+    }
+}
+
+impl Display for FailureReason {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Failure {
+    reason: FailureReason,
+    details: Box<str>,
+}
+
+impl Failure {
+    #[inline]
+    pub fn new<T>(reason: FailureReason, details: T) -> Self
+    where
+        T: Into<Box<str>>,
+    {
+        Self {
+            reason,
+            details: details.into(),
+        }
+    }
+
+    #[inline]
+    pub const fn reason(&self) -> FailureReason {
+        self.reason
+    }
+
+    #[inline]
+    pub const fn details(&self) -> &str {
+        &self.details
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}: {}", self.reason, self.details)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum StateChange {
     Unchanged,
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use tokio::fs;
+use tracing::{info, warn};
+
+use super::{InstanceName, StateChange};
+
+#[derive(Clone, Default)]
+pub(crate) struct Maintenance {
+    files: Box<[Box<Path>]>,
+    active: Option<usize>,
+}
+
+impl Maintenance {
+    #[inline]
+    pub const fn new(files: Box<[Box<Path>]>) -> Self {
+        Self {
+            files,
+            active: None,
+        }
+    }
+
+    #[inline]
+    pub fn active_file(&self) -> Option<&Path> {
+        self.active.map(|index| &*self.files[index])
+    }
+
+    #[inline]
+    pub fn carry_over(&mut self, previous: &Self) {
+        if self.files == previous.files {
+            self.active = previous.active;
+        }
+    }
+
+    pub async fn refresh(
+        &mut self,
+        InstanceName {
+            service_name,
+            instance_name,
+        }: InstanceName<'_>,
+    ) -> StateChange {
+        let mut active = None;
+
+        for (index, file) in self.files.iter().enumerate() {
+            match fs::try_exists(file).await {
+                Ok(true) => {
+                    active = Some(index);
+
+                    break;
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    warn!(
+                        "[{service_name:?}; {instance_name:?}] failed to check \
+                        for maintenance file {file:?}! Cause: {error}",
+                    );
+                }
+            }
+        }
+
+        if std::mem::replace(&mut self.active, active) == active {
+            return StateChange::Unchanged;
+        }
+
+        if let Some(file) = self.active_file() {
+            info!(
+                "[{service_name:?}; {instance_name:?}] put under maintenance, \
+                as {file:?} exists.",
+            );
+        } else {
+            info!(
+                "[{service_name:?}; {instance_name:?}] no longer under \
+                maintenance.",
+            );
+        }
+
+        StateChange::Changed
+    }
+}
@@ -0,0 +1,44 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::SystemTime,
+};
+
+use serde::Deserialize;
+
+#[derive(Clone, Copy)]
+pub(crate) struct ManualOverride {
+    pub mode: OverrideMode,
+    pub expires_at: Option<SystemTime>,
+}
+
+impl ManualOverride {
+    #[inline]
+    pub fn expired(&self, now: SystemTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum OverrideMode {
+    Drain,
+    ForceDown,
+    ForceUp,
+}
+
+impl OverrideMode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Drain => "drain",
+            Self::ForceDown => "force_down",
+            Self::ForceUp => "force_up",
+        }
+    }
+}
+
+impl Display for OverrideMode {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
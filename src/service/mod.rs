@@ -1,29 +1,45 @@
 use std::{
     collections::btree_map::{BTreeMap, Entry as BTreeMapEntry},
+    fmt::{Display, Formatter, Result as FmtResult},
     future::Future,
+    num::NonZeroU32,
+    path::Path,
+    time::SystemTime,
 };
 
 use anyhow::{anyhow, Result};
-use futures::{stream::FuturesUnordered, StreamExt as _, TryStreamExt as _};
+use futures::{
+    future::join_all, stream::FuturesUnordered, StreamExt as _,
+    TryStreamExt as _,
+};
 
 use crate::{
-    services::{generic_200_ok, node},
+    services::{generic_200_ok, grpc_health, node, tcp_connect},
+    snapshot::InstanceSnapshot,
     state::Epoch,
 };
 
 pub(crate) use self::{
-    configuration::Configurations,
+    configuration::{AllDownFallback, Configurations, OutputMode},
     instance::{
-        Configuration, Healthcheck, Instance, InstanceName, OutputVerbosity,
-        ServiceName, StateChange, Status,
+        CheckMode, Configuration, Failure, FailureReason, Healthcheck,
+        Instance, InstanceName, InstanceSettings, OutputVerbosity, ServiceName,
+        StateChange, Status, Thresholds, Timeouts,
     },
+    manual_override::{ManualOverride, OverrideMode},
+    statistics::Statistics,
 };
 
 mod configuration;
 mod instance;
+mod maintenance;
+mod manual_override;
+mod statistics;
 
 pub(crate) async fn from_configurations(
     configurations: Configurations,
+    timeouts: Timeouts,
+    mode: CheckMode,
 ) -> Result<Services> {
     let mut services = BTreeMap::new();
 
@@ -31,17 +47,75 @@ pub(crate) async fn from_configurations(
         match configuration {
             configuration::Configuration::Generic200Ok {
                 prepend,
+                settings,
                 instances,
             } => {
-                generic_service(prepend, instances)
-                    .await
-                    .map(|service| [(service_name, service)])
-                    .and_then(collect_services_from_iter(&mut services))?;
+                single_service(
+                    prepend,
+                    settings,
+                    timeouts,
+                    mode,
+                    instances,
+                    generic_200_ok::StorageConfiguration::create_instance,
+                    Instances::Generic200Ok,
+                )
+                .await
+                .map(|service| [(service_name, service)])
+                .and_then(collect_services_from_iter(&mut services))?;
             }
-            configuration::Configuration::Node { prepend, instances } => {
-                node_services(service_name.into_string(), prepend, instances)
-                    .await
-                    .and_then(collect_services_from_iter(&mut services))?;
+            configuration::Configuration::Node {
+                prepend,
+                settings,
+                service,
+                instances,
+            } => {
+                node_services(
+                    service_name.into_string(),
+                    prepend,
+                    settings,
+                    service,
+                    timeouts,
+                    mode,
+                    instances,
+                )
+                .await
+                .and_then(collect_services_from_iter(&mut services))?;
+            }
+            configuration::Configuration::GrpcHealth {
+                prepend,
+                settings,
+                instances,
+            } => {
+                single_service(
+                    prepend,
+                    settings,
+                    timeouts,
+                    mode,
+                    instances,
+                    grpc_health::StorageConfiguration::create_instance,
+                    Instances::GrpcHealth,
+                )
+                .await
+                .map(|service| [(service_name, service)])
+                .and_then(collect_services_from_iter(&mut services))?;
+            }
+            configuration::Configuration::TcpConnect {
+                prepend,
+                settings,
+                instances,
+            } => {
+                single_service(
+                    prepend,
+                    settings,
+                    timeouts,
+                    mode,
+                    instances,
+                    tcp_connect::StorageConfiguration::create_instance,
+                    Instances::TcpConnect,
+                )
+                .await
+                .map(|service| [(service_name, service)])
+                .and_then(collect_services_from_iter(&mut services))?;
             }
         }
     }
@@ -49,13 +123,117 @@ pub(crate) async fn from_configurations(
     Ok(services)
 }
 
+impl configuration::Settings {
+    fn maintenance_files<C>(
+        &self,
+        instance: &configuration::Instance<C>,
+    ) -> Box<[Box<Path>]> {
+        [&instance.maintenance_file, &self.maintenance_file]
+            .into_iter()
+            .flatten()
+            .map(|file| file.as_path().into())
+            .collect()
+    }
+
+    fn instance_settings<C>(
+        &self,
+        timeouts: Timeouts,
+        mode: CheckMode,
+        instance: &configuration::Instance<C>,
+    ) -> InstanceSettings {
+        InstanceSettings {
+            thresholds: match mode {
+                CheckMode::Continuous => Thresholds {
+                    rise: instance
+                        .rise
+                        .or(self.rise)
+                        .unwrap_or(NonZeroU32::MIN),
+                    fall: instance
+                        .fall
+                        .or(self.fall)
+                        .unwrap_or(NonZeroU32::MIN),
+                },
+                CheckMode::OneShot => Thresholds {
+                    rise: NonZeroU32::MIN,
+                    fall: NonZeroU32::MIN,
+                },
+            },
+            timeouts: Timeouts {
+                request: instance
+                    .timeout
+                    .or(self.timeout)
+                    .unwrap_or(timeouts.request),
+                connect: instance
+                    .connect_timeout
+                    .or(self.connect_timeout)
+                    .unwrap_or(timeouts.connect),
+            },
+            mode,
+        }
+    }
+}
+
+pub(crate) async fn carry_over(
+    services: &mut Services,
+    previous: &Services,
+) -> usize {
+    let mut carried_over = 0;
+
+    for (service_name, service) in services {
+        if let Some(previous) = previous.get(service_name) {
+            carried_over +=
+                service.instances.carry_over(&previous.instances).await;
+
+            service.carry_over_last_known_good(previous);
+        }
+    }
+
+    carried_over
+}
+
 pub(crate) type Services = BTreeMap<Box<str>, Service>;
 
 pub(crate) struct Service {
     prepend: Box<str>,
+    all_down_fallback: AllDownFallback,
+    output_mode: OutputMode,
+    last_known_good: Option<Box<[Box<str>]>>,
     instances: Instances,
 }
 
+impl Service {
+    #[inline]
+    fn new(
+        prepend: Box<str>,
+        settings: &configuration::Settings,
+        instances: Instances,
+    ) -> Self {
+        Self {
+            prepend,
+            all_down_fallback: settings.all_down_fallback.clone(),
+            output_mode: settings.output_mode,
+            last_known_good: None,
+            instances,
+        }
+    }
+
+    fn carry_over_last_known_good(&mut self, previous: &Self) {
+        self.last_known_good = previous
+            .last_known_good
+            .as_deref()
+            .map(|last_known_good| {
+                last_known_good
+                    .iter()
+                    .filter(|instance_name| {
+                        self.instances.contains_instance(instance_name)
+                    })
+                    .cloned()
+                    .collect::<Box<[_]>>()
+            })
+            .filter(|last_known_good| !last_known_good.is_empty());
+    }
+}
+
 macro_rules! define_instances {
     ($visibility:vis enum $enum:ident {
         $( $variant:ident < $configuration:ty , $state:ty $(,)? > ),+ $( , )?
@@ -72,6 +250,8 @@ define_instances! {
     pub(crate) enum Instances {
         Generic200Ok<generic_200_ok::Configuration, generic_200_ok::State>,
         Node<node::Configuration, node::State>,
+        GrpcHealth<grpc_health::Configuration, grpc_health::State>,
+        TcpConnect<tcp_connect::Configuration, tcp_connect::State>,
     }
 }
 
@@ -88,6 +268,16 @@ impl Instances {
                     .await
             }
             Self::Node(instances) => {
+                node::check_block_lag(instances, epoch).await;
+
+                Self::healthcheck_instances(instances, epoch, output_verbosity)
+                    .await
+            }
+            Self::GrpcHealth(instances) => {
+                Self::healthcheck_instances(instances, epoch, output_verbosity)
+                    .await
+            }
+            Self::TcpConnect(instances) => {
                 Self::healthcheck_instances(instances, epoch, output_verbosity)
                     .await
             }
@@ -112,34 +302,188 @@ impl Instances {
             .await
     }
 
+    async fn snapshot(&self) -> Box<[InstanceSnapshot]> {
+        match self {
+            Self::Generic200Ok(instances) => {
+                Self::snapshot_instances(instances).await
+            }
+            Self::Node(instances) => Self::snapshot_instances(instances).await,
+            Self::GrpcHealth(instances) => {
+                Self::snapshot_instances(instances).await
+            }
+            Self::TcpConnect(instances) => {
+                Self::snapshot_instances(instances).await
+            }
+        }
+    }
+
+    async fn snapshot_instances<C, S>(
+        instances: &[Instance<C, S>],
+    ) -> Box<[InstanceSnapshot]>
+    where
+        S: Healthcheck,
+    {
+        join_all(instances.iter().map(Instance::snapshot))
+            .await
+            .into_boxed_slice()
+    }
+
+    fn set_manual_override(
+        &mut self,
+        service_name: &str,
+        instance_name: &str,
+        manual_override: Option<ManualOverride>,
+    ) -> bool {
+        match self {
+            Self::Generic200Ok(instances) => {
+                Self::set_instance_manual_override(
+                    instances,
+                    service_name,
+                    instance_name,
+                    manual_override,
+                )
+            }
+            Self::Node(instances) => Self::set_instance_manual_override(
+                instances,
+                service_name,
+                instance_name,
+                manual_override,
+            ),
+            Self::GrpcHealth(instances) => Self::set_instance_manual_override(
+                instances,
+                service_name,
+                instance_name,
+                manual_override,
+            ),
+            Self::TcpConnect(instances) => Self::set_instance_manual_override(
+                instances,
+                service_name,
+                instance_name,
+                manual_override,
+            ),
+        }
+    }
+
+    fn set_instance_manual_override<C, S>(
+        instances: &mut [Instance<C, S>],
+        service_name: &str,
+        instance_name: &str,
+        manual_override: Option<ManualOverride>,
+    ) -> bool {
+        instances
+            .iter_mut()
+            .find(|instance| instance.instance_name() == instance_name)
+            .map(|instance| {
+                instance.set_manual_override(service_name, manual_override);
+            })
+            .is_some()
+    }
+
+    async fn refresh_overrides(
+        &mut self,
+        service_name: &str,
+        now: SystemTime,
+    ) -> StateChange {
+        match self {
+            Self::Generic200Ok(instances) => {
+                Self::refresh_instances_overrides(instances, service_name, now)
+                    .await
+            }
+            Self::Node(instances) => {
+                Self::refresh_instances_overrides(instances, service_name, now)
+                    .await
+            }
+            Self::GrpcHealth(instances) => {
+                Self::refresh_instances_overrides(instances, service_name, now)
+                    .await
+            }
+            Self::TcpConnect(instances) => {
+                Self::refresh_instances_overrides(instances, service_name, now)
+                    .await
+            }
+        }
+    }
+
+    async fn refresh_instances_overrides<C, S>(
+        instances: &mut [Instance<C, S>],
+        service_name: &str,
+        now: SystemTime,
+    ) -> StateChange {
+        instances
+            .iter_mut()
+            .map(|instance| instance.refresh_overrides(service_name, now))
+            .collect::<FuturesUnordered<_>>()
+            .fold(StateChange::Unchanged, |accumulated, instance| async move {
+                accumulated & instance
+            })
+            .await
+    }
+
+    async fn carry_over(&mut self, previous: &Self) -> usize {
+        match (self, previous) {
+            (Self::Generic200Ok(instances), Self::Generic200Ok(previous)) => {
+                Self::carry_over_instances(instances, previous).await
+            }
+            (Self::Node(instances), Self::Node(previous)) => {
+                Self::carry_over_instances(instances, previous).await
+            }
+            (Self::GrpcHealth(instances), Self::GrpcHealth(previous)) => {
+                Self::carry_over_instances(instances, previous).await
+            }
+            (Self::TcpConnect(instances), Self::TcpConnect(previous)) => {
+                Self::carry_over_instances(instances, previous).await
+            }
+            _ => 0,
+        }
+    }
+
+    async fn carry_over_instances<C, S>(
+        instances: &mut [Instance<C, S>],
+        previous: &[Instance<C, S>],
+    ) -> usize
+    where
+        S: Healthcheck,
+    {
+        let mut carried_over = 0;
+
+        for instance in instances {
+            for previous in previous {
+                if instance.carry_over(previous).await {
+                    carried_over += 1;
+
+                    break;
+                }
+            }
+        }
+
+        carried_over
+    }
+
     #[inline]
     pub async fn write_out<W>(
         &self,
         mut writer: W,
-        global_prepend: &str,
-        prepend: &str,
+        settings: WriteOutSettings<'_>,
     ) -> Result<WriteOutStatus>
     where
         W: ServiceOutputWriter,
     {
         match self {
             Self::Generic200Ok(instances) => {
-                Self::write_out_instances(
-                    &mut writer,
-                    instances,
-                    global_prepend,
-                    prepend,
-                )
-                .await
+                Self::write_out_instances(&mut writer, instances, settings)
+                    .await
             }
             Self::Node(instances) => {
-                Self::write_out_instances(
-                    &mut writer,
-                    instances,
-                    global_prepend,
-                    prepend,
-                )
-                .await
+                Self::write_out_instances(&mut writer, instances, settings)
+                    .await
+            }
+            Self::GrpcHealth(instances) => {
+                Self::write_out_instances(&mut writer, instances, settings)
+                    .await
+            }
+            Self::TcpConnect(instances) => {
+                Self::write_out_instances(&mut writer, instances, settings)
+                    .await
             }
         }
     }
@@ -147,15 +491,18 @@ impl Instances {
     async fn write_out_instances<W, C, S>(
         writer: &mut W,
         instances: &[Instance<C, S>],
-        global_prepend: &str,
-        prepend: &str,
+        WriteOutSettings {
+            global_prepend,
+            prepend,
+            all_down_fallback,
+            last_known_good,
+            output_mode,
+        }: WriteOutSettings<'_>,
     ) -> Result<WriteOutStatus>
     where
         W: ServiceOutputWriter,
         C: Configuration,
     {
-        let mut healthy_instances = 0;
-
         for prepend in [global_prepend, prepend]
             .iter()
             .copied()
@@ -164,28 +511,215 @@ impl Instances {
             writer.write_out_prepended(prepend).await?;
         }
 
-        for output in instances
+        let healthy_instances = instances
             .iter()
             .filter(|instance| matches!(instance.enabled(), Status::Enabled))
-            .map(|instance| instance.configuration().output())
-        {
-            healthy_instances += 1;
+            .count();
+
+        let fallback = (healthy_instances == 0).then_some(
+            match (all_down_fallback, last_known_good) {
+                (AllDownFallback::LastKnownGood, Some(_)) => {
+                    AppliedFallback::LastKnownGood
+                }
+                (AllDownFallback::LastKnownGood, None)
+                | (AllDownFallback::AllInstances, _) => {
+                    AppliedFallback::AllInstances
+                }
+                (AllDownFallback::Sentinel(_), _) => AppliedFallback::Sentinel,
+            },
+        );
+
+        let mut written_up = false;
+
+        let mut written = false;
+
+        for instance in instances {
+            let override_mode = instance.override_mode();
+
+            let excluded = override_mode.map_or_else(
+                || instance.maintenance_file().is_some(),
+                |mode| mode == OverrideMode::ForceDown,
+            );
+
+            let up = match (instance.enabled(), fallback) {
+                _ if excluded => false,
+                (Status::Enabled, _)
+                | (Status::Disabled, Some(AppliedFallback::AllInstances)) => {
+                    true
+                }
+                (Status::Disabled, Some(AppliedFallback::LastKnownGood)) => {
+                    last_known_good.is_some_and(|last_known_good| {
+                        last_known_good.iter().any(|instance_name| {
+                            **instance_name == *instance.instance_name()
+                        })
+                    })
+                }
+                (
+                    Status::Disabled,
+                    None
+                    | Some(
+                        AppliedFallback::MarkedDown | AppliedFallback::Sentinel,
+                    ),
+                ) => false,
+            };
+
+            let output = instance.configuration().output();
+
+            if up {
+                writer.write_out_entry(output, EntryState::Up).await?;
+
+                written_up = true;
+
+                written = true;
+            } else if let OutputMode::MarkDown = output_mode {
+                writer
+                    .write_out_entry(output, Self::down_entry_state(instance))
+                    .await?;
+
+                written = true;
+            }
+        }
+
+        let fallback = match fallback {
+            Some(
+                AppliedFallback::LastKnownGood | AppliedFallback::AllInstances,
+            ) if !written_up => {
+                if !written {
+                    for instance in instances {
+                        writer
+                            .write_out_entry(
+                                instance.configuration().output(),
+                                Self::down_entry_state(instance),
+                            )
+                            .await?;
+                    }
+                }
+
+                Some(AppliedFallback::MarkedDown)
+            }
+            fallback => fallback,
+        };
+
+        if let AllDownFallback::Sentinel(sentinel) = all_down_fallback {
+            if let Some(AppliedFallback::Sentinel) = fallback {
+                writer.write_out_entry(sentinel, EntryState::Up).await?;
+            }
+        }
+
+        Ok(WriteOutStatus {
+            healthy_instances,
+            fallback,
+        })
+    }
+
+    fn down_entry_state<C, S>(instance: &Instance<C, S>) -> EntryState<'_> {
+        instance
+            .override_mode()
+            .map(EntryState::Overridden)
+            .unwrap_or_else(|| {
+                instance.maintenance_file().map_or(
+                    EntryState::Down(instance.last_failure()),
+                    EntryState::Maintenance,
+                )
+            })
+    }
+
+    fn healthy_instances(&self) -> Box<[Box<str>]> {
+        match self {
+            Self::Generic200Ok(instances) => {
+                Self::healthy_instances_names(instances)
+            }
+            Self::Node(instances) => Self::healthy_instances_names(instances),
+            Self::GrpcHealth(instances) => {
+                Self::healthy_instances_names(instances)
+            }
+            Self::TcpConnect(instances) => {
+                Self::healthy_instances_names(instances)
+            }
+        }
+    }
+
+    fn healthy_instances_names<C, S>(
+        instances: &[Instance<C, S>],
+    ) -> Box<[Box<str>]> {
+        instances
+            .iter()
+            .filter(|instance| matches!(instance.enabled(), Status::Enabled))
+            .map(|instance| instance.instance_name().into())
+            .collect()
+    }
 
-            writer.write_out_entry(output).await?;
+    fn contains_instance(&self, instance_name: &str) -> bool {
+        match self {
+            Self::Generic200Ok(instances) => {
+                Self::contains_instance_named(instances, instance_name)
+            }
+            Self::Node(instances) => {
+                Self::contains_instance_named(instances, instance_name)
+            }
+            Self::GrpcHealth(instances) => {
+                Self::contains_instance_named(instances, instance_name)
+            }
+            Self::TcpConnect(instances) => {
+                Self::contains_instance_named(instances, instance_name)
+            }
         }
+    }
 
-        Ok(WriteOutStatus { healthy_instances })
+    fn contains_instance_named<C, S>(
+        instances: &[Instance<C, S>],
+        instance_name: &str,
+    ) -> bool {
+        instances
+            .iter()
+            .any(|instance| instance.instance_name() == instance_name)
     }
 }
 
 impl Service {
-    #[inline]
-    pub fn healthcheck<'r>(
-        &'r mut self,
+    pub async fn healthcheck(
+        &mut self,
         epoch: Epoch,
-        output_verbosity: OutputVerbosity<ServiceName<'r>>,
-    ) -> impl Future<Output = StateChange> + Send + 'r {
-        self.instances.healthcheck(epoch, output_verbosity)
+        output_verbosity: OutputVerbosity<ServiceName<'_>>,
+    ) -> StateChange {
+        let state_change =
+            self.instances.healthcheck(epoch, output_verbosity).await;
+
+        let healthy_instances = self.instances.healthy_instances();
+
+        if !healthy_instances.is_empty() {
+            self.last_known_good = Some(healthy_instances);
+        }
+
+        state_change
+    }
+
+    #[inline]
+    pub async fn snapshot(&self) -> Box<[InstanceSnapshot]> {
+        self.instances.snapshot().await
+    }
+
+    #[inline]
+    pub fn set_manual_override(
+        &mut self,
+        service_name: &str,
+        instance_name: &str,
+        manual_override: Option<ManualOverride>,
+    ) -> bool {
+        self.instances.set_manual_override(
+            service_name,
+            instance_name,
+            manual_override,
+        )
+    }
+
+    #[inline]
+    pub async fn refresh_overrides(
+        &mut self,
+        service_name: &str,
+        now: SystemTime,
+    ) -> StateChange {
+        self.instances.refresh_overrides(service_name, now).await
     }
 
     #[inline]
@@ -197,13 +731,53 @@ impl Service {
     where
         W: ServiceOutputWriter + 'r,
     {
-        self.instances
-            .write_out(writer, global_prepend, &self.prepend)
+        self.instances.write_out(
+            writer,
+            WriteOutSettings {
+                global_prepend,
+                prepend: &self.prepend,
+                all_down_fallback: &self.all_down_fallback,
+                last_known_good: self.last_known_good.as_deref(),
+                output_mode: self.output_mode,
+            },
+        )
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) struct WriteOutSettings<'r> {
+    global_prepend: &'r str,
+    prepend: &'r str,
+    all_down_fallback: &'r AllDownFallback,
+    last_known_good: Option<&'r [Box<str>]>,
+    output_mode: OutputMode,
+}
+
 pub(crate) struct WriteOutStatus {
     pub healthy_instances: usize,
+    pub fallback: Option<AppliedFallback>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum AppliedFallback {
+    LastKnownGood,
+    AllInstances,
+    MarkedDown,
+    Sentinel,
+}
+
+impl Display for AppliedFallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::LastKnownGood => "the last known good instances",
+            Self::AllInstances => "all instances",
+            Self::MarkedDown => {
+                "all instances marked as down, as none can be written out \
+                as up"
+            }
+            Self::Sentinel => "the sentinel entry",
+        })
+    }
 }
 
 pub(crate) trait ServiceOutputWriter {
@@ -215,10 +789,19 @@ pub(crate) trait ServiceOutputWriter {
     fn write_out_entry<'r>(
         &'r mut self,
         output: &'r str,
+        state: EntryState<'r>,
     ) -> impl Future<Output = Result<()>> + 'r;
 }
 
-impl<'r, T> ServiceOutputWriter for &'r mut T
+#[derive(Clone, Copy)]
+pub(crate) enum EntryState<'r> {
+    Up,
+    Down(Option<&'r Failure>),
+    Overridden(OverrideMode),
+    Maintenance(&'r Path),
+}
+
+impl<T> ServiceOutputWriter for &mut T
 where
     T: ServiceOutputWriter,
 {
@@ -234,8 +817,9 @@ where
     fn write_out_entry<'t>(
         &'t mut self,
         output: &'t str,
+        state: EntryState<'t>,
     ) -> impl Future<Output = Result<()>> + 't {
-        T::write_out_entry(self, output)
+        T::write_out_entry(self, output, state)
     }
 }
 
@@ -250,7 +834,9 @@ async fn map_and_collect_futures<
     map: Map,
 ) -> Result<Vec<Output>, Error>
 where
-    Map: FnMut((Box<str>, StorageConfiguration)) -> MapFuture,
+    Map: FnMut(
+        (Box<str>, configuration::Instance<StorageConfiguration>),
+    ) -> MapFuture,
     MapFuture: Future<Output = Result<Output, Error>>,
 {
     configuration
@@ -261,28 +847,84 @@ where
         .await
 }
 
-async fn generic_service(
+async fn single_service<
+    StorageConfiguration,
+    Create,
+    CreateFuture,
+    Variant,
+    C,
+    S,
+>(
     prepend: Box<str>,
-    configuration: configuration::Instances<
-        generic_200_ok::StorageConfiguration,
-    >,
-) -> Result<Service> {
+    settings: configuration::Settings,
+    timeouts: Timeouts,
+    mode: CheckMode,
+    configuration: configuration::Instances<StorageConfiguration>,
+    mut create_instance: Create,
+    variant: Variant,
+) -> Result<Service>
+where
+    Create:
+        FnMut(StorageConfiguration, Box<str>, InstanceSettings) -> CreateFuture,
+    CreateFuture: Future<Output = Result<Instance<C, S>>>,
+    Variant: FnOnce(Box<[Instance<C, S>]>) -> Instances,
+{
     map_and_collect_futures(configuration, |(instance_name, configuration)| {
-        configuration.create_instance(instance_name)
+        let instance_settings =
+            settings.instance_settings(timeouts, mode, &configuration);
+
+        let maintenance_files = settings.maintenance_files(&configuration);
+
+        let instance = create_instance(
+            configuration.configuration,
+            instance_name,
+            instance_settings,
+        );
+
+        async move {
+            instance.await.map(|instance| {
+                instance.with_maintenance_files(maintenance_files)
+            })
+        }
     })
     .await
     .map(Vec::into_boxed_slice)
-    .map(Instances::Generic200Ok)
-    .map(|instances| Service { prepend, instances })
+    .map(variant)
+    .map(|instances| Service::new(prepend, &settings, instances))
 }
 
 async fn node_services(
     service_name: String,
     prepend: Box<str>,
+    settings: configuration::Settings,
+    service: node::ServiceConfiguration,
+    timeouts: Timeouts,
+    mode: CheckMode,
     configuration: configuration::Instances<node::StorageConfiguration>,
 ) -> Result<[(Box<str>, Service); 3]> {
     map_and_collect_futures(configuration, |(instance_name, configuration)| {
-        configuration.create_instance(instance_name)
+        let instance_settings =
+            settings.instance_settings(timeouts, mode, &configuration);
+
+        let maintenance_files = settings.maintenance_files(&configuration);
+
+        let instances = configuration.configuration.create_instance(
+            instance_name,
+            instance_settings,
+            &service,
+        );
+
+        async move {
+            instances.await.map(|instances| node::Instances {
+                lcd: instances
+                    .lcd
+                    .with_maintenance_files(maintenance_files.clone()),
+                json_rpc: instances
+                    .json_rpc
+                    .with_maintenance_files(maintenance_files.clone()),
+                grpc: instances.grpc.with_maintenance_files(maintenance_files),
+            })
+        }
     })
     .await
     .map(|instances| {
@@ -305,24 +947,20 @@ async fn node_services(
     })
     .map(|instances| {
         [
-            (service_name.clone(), "_lcd", prepend.clone(), instances.lcd),
-            (
-                service_name.clone(),
-                "_rpc",
-                prepend.clone(),
-                instances.json_rpc,
-            ),
-            (service_name, "_grpc", prepend, instances.grpc),
+            (service_name.clone(), "_lcd", instances.lcd),
+            (service_name.clone(), "_rpc", instances.json_rpc),
+            (service_name, "_grpc", instances.grpc),
         ]
-        .map(|(mut service_name, suffix, prepend, instances)| {
+        .map(|(mut service_name, suffix, instances)| {
             service_name.push_str(suffix);
 
             (
                 service_name.into_boxed_str(),
-                Service {
-                    prepend,
-                    instances: Instances::Node(instances.into_boxed_slice()),
-                },
+                Service::new(
+                    prepend.clone(),
+                    &settings,
+                    Instances::Node(instances.into_boxed_slice()),
+                ),
             )
         })
     })
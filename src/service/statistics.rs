@@ -0,0 +1,103 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use super::FailureReason;
+
+#[derive(Clone, Default)]
+pub(crate) struct Statistics {
+    latency: Histogram,
+    failures: BTreeMap<FailureReason, u64>,
+    last_check: Option<LastCheck>,
+}
+
+impl Statistics {
+    #[inline]
+    pub const fn latency(&self) -> &Histogram {
+        &self.latency
+    }
+
+    #[inline]
+    pub const fn failures(&self) -> &BTreeMap<FailureReason, u64> {
+        &self.failures
+    }
+
+    #[inline]
+    pub const fn last_check(&self) -> Option<&LastCheck> {
+        self.last_check.as_ref()
+    }
+
+    pub(super) fn register(
+        &mut self,
+        latency: Duration,
+        failure: Option<FailureReason>,
+    ) {
+        self.latency.observe(latency.as_secs_f64());
+
+        self.last_check = Some(LastCheck {
+            checked_at: SystemTime::now(),
+            latency,
+        });
+
+        if let Some(reason) = failure {
+            *self.failures.entry(reason).or_default() += 1;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct LastCheck {
+    pub checked_at: SystemTime,
+    pub latency: Duration,
+}
+
+#[derive(Clone)]
+pub(crate) struct Histogram {
+    buckets: [u64; Self::BOUNDS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    pub const BOUNDS: [f64; 11] = [
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ];
+
+    #[inline]
+    pub fn buckets(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        Self::BOUNDS.into_iter().zip(self.buckets)
+    }
+
+    #[inline]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    #[inline]
+    pub const fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    fn observe(&mut self, value: f64) {
+        Self::BOUNDS
+            .iter()
+            .zip(&mut self.buckets)
+            .filter(|&(&bound, _)| value <= bound)
+            .for_each(|(_, bucket)| *bucket += 1);
+
+        self.count += 1;
+
+        self.sum += value;
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: [0; Self::BOUNDS.len()],
+            count: 0,
+            sum: 0.0,
+        }
+    }
+}
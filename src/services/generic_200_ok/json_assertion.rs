@@ -0,0 +1,152 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(super) struct JsonAssertion {
+    pointer: Box<str>,
+    #[serde(flatten)]
+    condition: Condition,
+}
+
+impl JsonAssertion {
+    pub fn check(&self, document: &Value) -> Result<(), Box<str>> {
+        let Some(value) = document.pointer(&self.pointer) else {
+            return Err(format!("{self} (value is missing)").into());
+        };
+
+        let satisfied = match &self.condition {
+            Condition::Equals(expected) => value == expected,
+            Condition::NotEquals(expected) => value != expected,
+            Condition::LessThan(expected) => {
+                Self::compare(value, |value| value < *expected)
+            }
+            Condition::LessThanOrEqual(expected) => {
+                Self::compare(value, |value| value <= *expected)
+            }
+            Condition::GreaterThan(expected) => {
+                Self::compare(value, |value| value > *expected)
+            }
+            Condition::GreaterThanOrEqual(expected) => {
+                Self::compare(value, |value| value >= *expected)
+            }
+        };
+
+        if satisfied {
+            Ok(())
+        } else {
+            Err(format!("{self} (found {value})").into())
+        }
+    }
+
+    fn compare<F>(value: &Value, f: F) -> bool
+    where
+        F: FnOnce(f64) -> bool,
+    {
+        value.as_f64().is_some_and(f)
+    }
+}
+
+impl Display for JsonAssertion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let (operator, expected): (_, &dyn Display) = match &self.condition {
+            Condition::Equals(expected) => ("==", expected),
+            Condition::NotEquals(expected) => ("!=", expected),
+            Condition::LessThan(expected) => ("<", expected),
+            Condition::LessThanOrEqual(expected) => ("<=", expected),
+            Condition::GreaterThan(expected) => (">", expected),
+            Condition::GreaterThanOrEqual(expected) => (">=", expected),
+        };
+
+        write!(f, "{:?} {operator} {expected}", self.pointer)
+    }
+}
+
+#[derive(PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+enum Condition {
+    Equals(Value),
+    NotEquals(Value),
+    LessThan(f64),
+    LessThanOrEqual(f64),
+    GreaterThan(f64),
+    GreaterThanOrEqual(f64),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::JsonAssertion;
+
+    fn assertion(assertion: Value) -> JsonAssertion {
+        serde_json::from_value(assertion).unwrap()
+    }
+
+    fn document() -> Value {
+        json!({
+            "status": "ok",
+            "sync": { "height": 100, "catching_up": false },
+        })
+    }
+
+    #[test]
+    fn checks_equality() {
+        assert!(assertion(json!({ "pointer": "/status", "equals": "ok" }))
+            .check(&document())
+            .is_ok());
+
+        assert!(assertion(json!({
+            "pointer": "/sync/catching_up",
+            "not_equals": true,
+        }))
+        .check(&document())
+        .is_ok());
+
+        let failure =
+            assertion(json!({ "pointer": "/status", "equals": "degraded" }))
+                .check(&document())
+                .unwrap_err();
+
+        assert_eq!(&*failure, r#""/status" == "degraded" (found "ok")"#);
+    }
+
+    #[test]
+    fn checks_comparisons() {
+        for (condition, satisfied) in [
+            ("less_than", false),
+            ("less_than_or_equal", true),
+            ("greater_than", false),
+            ("greater_than_or_equal", true),
+        ] {
+            assert_eq!(
+                assertion(json!({ "pointer": "/sync/height", condition: 100 }))
+                    .check(&document())
+                    .is_ok(),
+                satisfied,
+                "{condition}",
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_numeric_comparisons() {
+        assert!(
+            assertion(json!({ "pointer": "/status", "greater_than": 0 }))
+                .check(&document())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_missing_values() {
+        let failure =
+            assertion(json!({ "pointer": "/sync/missing", "equals": 1 }))
+                .check(&document())
+                .unwrap_err();
+
+        assert_eq!(&*failure, r#""/sync/missing" == 1 (value is missing)"#);
+    }
+}
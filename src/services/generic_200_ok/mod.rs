@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use regex::Regex;
+use reqwest::{Client as ReqwestClient, Response as ReqwestResponse, Url};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    http_client::{self, http_client},
+    serde::{deserialize_boxed_string, deserialize_optional_regex},
+    service::{
+        self, Failure, FailureReason, Instance, InstanceName, InstanceSettings,
+        OutputVerbosity,
+    },
+    state::Epoch,
+};
+
+use self::json_assertion::JsonAssertion;
+
+mod json_assertion;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct StorageConfiguration {
+    #[serde(with = "crate::url")]
+    healthcheck_url: Url,
+    #[serde(deserialize_with = "deserialize_boxed_string")]
+    output: Box<str>,
+    #[serde(default)]
+    accepted_status_codes: Option<Box<[u16]>>,
+    #[serde(default)]
+    body_contains: Option<Box<str>>,
+    #[serde(default, deserialize_with = "deserialize_optional_regex")]
+    body_matches: Option<Regex>,
+    #[serde(default)]
+    json_assertions: Box<[JsonAssertion]>,
+    #[serde(default = "default_max_body_bytes")]
+    max_body_bytes: usize,
+}
+
+struct Assertions {
+    accepted_status_codes: Option<Box<[u16]>>,
+    body_contains: Option<Box<str>>,
+    body_matches: Option<Regex>,
+    json_assertions: Box<[JsonAssertion]>,
+    max_body_bytes: usize,
+}
+
+impl PartialEq for Assertions {
+    fn eq(&self, other: &Self) -> bool {
+        self.accepted_status_codes == other.accepted_status_codes
+            && self.body_contains == other.body_contains
+            && self.body_matches.as_ref().map(Regex::as_str)
+                == other.body_matches.as_ref().map(Regex::as_str)
+            && self.json_assertions == other.json_assertions
+            && self.max_body_bytes == other.max_body_bytes
+    }
+}
+
+impl Assertions {
+    const fn inspects_body(&self) -> bool {
+        self.body_contains.is_some()
+            || self.body_matches.is_some()
+            || !self.json_assertions.is_empty()
+    }
+
+    fn check_status(
+        &self,
+        response: ReqwestResponse,
+    ) -> Result<ReqwestResponse, Failure> {
+        let Some(accepted_status_codes) = &self.accepted_status_codes else {
+            return response.error_for_status().map_err(http_client::failure);
+        };
+
+        let status = response.status();
+
+        if accepted_status_codes.contains(&status.as_u16()) {
+            Ok(response)
+        } else {
+            Err(Failure::new(
+                FailureReason::Status,
+                format!(
+                    "Response status {status} is not one of the accepted \
+                    status codes, {accepted_status_codes:?}.",
+                ),
+            ))
+        }
+    }
+
+    fn check_body(&self, body: &str) -> Result<(), Failure> {
+        if let Some(body_contains) = self
+            .body_contains
+            .as_deref()
+            .filter(|&body_contains| !body.contains(body_contains))
+        {
+            Err(Failure::new(
+                FailureReason::Response,
+                format!(
+                    "Response body does not contain {body_contains:?}. \
+                    Received: {:?}",
+                    truncated(body),
+                ),
+            ))
+        } else if let Some(body_matches) = self
+            .body_matches
+            .as_ref()
+            .filter(|body_matches| !body_matches.is_match(body))
+        {
+            Err(Failure::new(
+                FailureReason::Response,
+                format!(
+                    "Response body does not match pattern {:?}. Received: \
+                    {:?}",
+                    body_matches.as_str(),
+                    truncated(body),
+                ),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_json(&self, body: &[u8]) -> Result<(), Failure> {
+        if self.json_assertions.is_empty() {
+            return Ok(());
+        }
+
+        let document: Value =
+            serde_json::from_slice(body).map_err(|error| {
+                Failure::new(
+                    FailureReason::Response,
+                    format!("Response body is not valid JSON! Cause: {error}"),
+                )
+            })?;
+
+        let failed: Vec<_> = self
+            .json_assertions
+            .iter()
+            .filter_map(|assertion| assertion.check(&document).err())
+            .collect();
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(Failure::new(
+                FailureReason::Response,
+                format!("JSON assertions failed: {}", failed.join("; ")),
+            ))
+        }
+    }
+}
+
+impl StorageConfiguration {
+    pub(crate) async fn create_instance(
+        self,
+        instance_name: Box<str>,
+        InstanceSettings {
+            thresholds,
+            timeouts,
+            mode,
+        }: InstanceSettings,
+    ) -> Result<Instance<Configuration, State>> {
+        let client = http_client(timeouts)?;
+
+        let state = State {
+            client,
+            healthcheck_url: self.healthcheck_url,
+            assertions: Arc::new(Assertions {
+                accepted_status_codes: self.accepted_status_codes,
+                body_contains: self.body_contains,
+                body_matches: self.body_matches,
+                json_assertions: self.json_assertions,
+                max_body_bytes: self.max_body_bytes,
+            }),
+        };
+
+        Ok(Instance::new(
+            instance_name,
+            mode.initial_result(state.healthcheck()).await,
+            thresholds,
+            Configuration {
+                output: self.output,
+            },
+            state,
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Configuration {
+    output: Box<str>,
+}
+
+impl service::Configuration for Configuration {
+    fn output(&self) -> &str {
+        &self.output
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct State {
+    client: ReqwestClient,
+    healthcheck_url: Url,
+    assertions: Arc<Assertions>,
+}
+
+impl State {
+    async fn healthcheck(&self) -> Result<(), Failure> {
+        let response = self
+            .client
+            .get(self.healthcheck_url.clone())
+            .send()
+            .await
+            .map_err(http_client::failure)?;
+
+        let mut response = self.assertions.check_status(response)?;
+
+        if !self.assertions.inspects_body() {
+            return Ok(());
+        }
+
+        let mut body = Vec::new();
+
+        while let Some(chunk) =
+            response.chunk().await.map_err(http_client::failure)?
+        {
+            if self.assertions.max_body_bytes - body.len() < chunk.len() {
+                return Err(Failure::new(
+                    FailureReason::Response,
+                    format!(
+                        "Response body exceeds max_body_bytes ({}).",
+                        self.assertions.max_body_bytes,
+                    ),
+                ));
+            }
+
+            body.extend_from_slice(&chunk);
+        }
+
+        self.assertions
+            .check_body(&String::from_utf8_lossy(&body))
+            .and_then(|()| self.assertions.check_json(&body))
+    }
+}
+
+impl service::Healthcheck for State {
+    #[inline]
+    async fn healthcheck(
+        &mut self,
+        _: Epoch,
+        output_verbosity: OutputVerbosity<InstanceName<'_>>,
+    ) -> Result<(), Failure> {
+        let result = Self::healthcheck(self).await;
+
+        output_verbosity.report(&result);
+
+        result
+    }
+
+    #[inline]
+    fn same_target(&self, previous: &Self) -> bool {
+        self.healthcheck_url == previous.healthcheck_url
+            && self.assertions == previous.assertions
+    }
+
+    #[inline]
+    async fn carry_over(&mut self, _: &Self) {}
+}
+
+const fn default_max_body_bytes() -> usize {
+    1 << 20
+}
+
+fn truncated(body: &str) -> &str {
+    const LIMIT: usize = 256;
+
+    if body.len() <= LIMIT {
+        body
+    } else {
+        let mut end = LIMIT;
+
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        &body[..end]
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use anyhow::Result;
+use reqwest::{Client as ReqwestClient, Url};
+use serde::Deserialize;
+
+use crate::{
+    grpc,
+    http_client::grpc_client,
+    serde::deserialize_boxed_string,
+    service::{
+        self, Failure, FailureReason, Instance, InstanceName, InstanceSettings,
+        OutputVerbosity,
+    },
+    state::Epoch,
+    url::join_relative,
+};
+
+const CHECK_PATH: &str = "grpc.health.v1.Health/Check";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct StorageConfiguration {
+    #[serde(with = "crate::url")]
+    url: Url,
+    #[serde(default)]
+    service: Option<Box<str>>,
+    #[serde(deserialize_with = "deserialize_boxed_string")]
+    output: Box<str>,
+}
+
+impl StorageConfiguration {
+    pub(crate) async fn create_instance(
+        self,
+        instance_name: Box<str>,
+        InstanceSettings {
+            thresholds,
+            timeouts,
+            mode,
+        }: InstanceSettings,
+    ) -> Result<Instance<Configuration, State>> {
+        let client = grpc_client(timeouts)?;
+
+        let state = State {
+            client,
+            url: join_relative(&self.url, CHECK_PATH)?,
+            request: encode_request(self.service.as_deref().unwrap_or("")),
+        };
+
+        Ok(Instance::new(
+            instance_name,
+            mode.initial_result(state.healthcheck()).await,
+            thresholds,
+            Configuration {
+                output: self.output,
+            },
+            state,
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Configuration {
+    output: Box<str>,
+}
+
+impl service::Configuration for Configuration {
+    fn output(&self) -> &str {
+        &self.output
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct State {
+    client: ReqwestClient,
+    url: Url,
+    request: Box<[u8]>,
+}
+
+impl State {
+    async fn healthcheck(&self) -> Result<(), Failure> {
+        let response =
+            grpc::unary(&self.client, self.url.clone(), &self.request).await?;
+
+        match grpc::varint_field(&response, 1)?
+            .map_or(ServingStatus::Unknown, ServingStatus::from)
+        {
+            ServingStatus::Serving => Ok(()),
+            status => Err(Failure::new(
+                FailureReason::NotServing,
+                format!("Service reported status {status}."),
+            )),
+        }
+    }
+}
+
+impl service::Healthcheck for State {
+    #[inline]
+    async fn healthcheck(
+        &mut self,
+        _: Epoch,
+        output_verbosity: OutputVerbosity<InstanceName<'_>>,
+    ) -> Result<(), Failure> {
+        let result = Self::healthcheck(self).await;
+
+        output_verbosity.report(&result);
+
+        result
+    }
+
+    #[inline]
+    fn same_target(&self, previous: &Self) -> bool {
+        self.url == previous.url && self.request == previous.request
+    }
+
+    #[inline]
+    async fn carry_over(&mut self, _: &Self) {}
+}
+
+#[derive(Clone, Copy)]
+enum ServingStatus {
+    Unknown,
+    Serving,
+    NotServing,
+    ServiceUnknown,
+    Other(u64),
+}
+
+impl From<u64> for ServingStatus {
+    fn from(value: u64) -> Self {
+        match value {
+            0 => Self::Unknown,
+            1 => Self::Serving,
+            2 => Self::NotServing,
+            3 => Self::ServiceUnknown,
+            value => Self::Other(value),
+        }
+    }
+}
+
+impl Display for ServingStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Unknown => f.write_str("UNKNOWN"),
+            Self::Serving => f.write_str("SERVING"),
+            Self::NotServing => f.write_str("NOT_SERVING"),
+            Self::ServiceUnknown => f.write_str("SERVICE_UNKNOWN"),
+            Self::Other(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+fn encode_request(service: &str) -> Box<[u8]> {
+    let mut message = vec![];
+
+    if !service.is_empty() {
+        grpc::encode_string_field(&mut message, 1, service);
+    }
+
+    grpc::encode_request(&message)
+}
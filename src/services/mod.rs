@@ -1,2 +1,4 @@
 pub mod generic_200_ok;
+pub mod grpc_health;
 pub mod node;
+pub mod tcp_connect;
@@ -1,21 +1,47 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
 use anyhow::Result;
-use reqwest::{Client as ReqwestClient, Response as ReqwestResponse, Url};
+use futures::future::join_all;
+use reqwest::{
+    Client as ReqwestClient, Error as ReqwestError,
+    Response as ReqwestResponse, Url,
+};
 use serde::Deserialize;
-use tokio::sync::Mutex;
-use tracing::info;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::warn;
 
 use crate::{
-    http_client::http_client,
-    serde::deserialize_boxed_string,
-    service::{self, Instance, InstanceName, OutputVerbosity, Status},
+    grpc,
+    http_client::{self, grpc_client, http_client},
+    serde::{
+        deserialize_boxed_string, deserialize_optional_seconds,
+        deserialize_seconds,
+    },
+    service::{
+        self, CheckMode, Failure, FailureReason, Instance, InstanceName,
+        InstanceSettings, OutputVerbosity,
+    },
     state::Epoch,
+    url::join_relative,
 };
 
-use self::status_response::StatusResponse;
+use self::{
+    status_response::StatusResponse, syncing_response::SyncingResponse,
+};
 
 mod status_response;
+mod syncing_response;
+
+const DEFAULT_STATUS_PATH: &str = "status";
+
+const LCD_SYNCING_PATH: &str = "cosmos/base/tendermint/v1beta1/syncing";
+
+const GRPC_SYNCING_PATH: &str =
+    "cosmos.base.tendermint.v1beta1.Service/GetSyncing";
 
 pub(crate) struct Instances<T> {
     pub lcd: T,
@@ -23,11 +49,45 @@ pub(crate) struct Instances<T> {
     pub grpc: T,
 }
 
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct ServiceConfiguration {
+    #[serde(default)]
+    max_block_lag: Option<u64>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_seconds",
+        rename = "max_block_age_seconds"
+    )]
+    max_block_age: Option<Duration>,
+    #[serde(default)]
+    stall_tolerance: Option<StallTolerance>,
+    #[serde(default)]
+    chain_id: Option<Box<str>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+enum StallTolerance {
+    Cycles(u32),
+    Seconds(#[serde(deserialize_with = "deserialize_seconds")] Duration),
+}
+
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub(crate) struct StorageConfiguration {
     #[serde(with = "crate::url")]
     json_rpc_url: Url,
+    #[serde(default)]
+    status_path: Option<Box<str>>,
+    #[serde(default, deserialize_with = "crate::url::deserialize_optional")]
+    lcd_url: Option<Url>,
+    #[serde(default, deserialize_with = "crate::url::deserialize_optional")]
+    grpc_url: Option<Url>,
+    #[serde(default)]
+    expected_chain_id: Option<Box<str>>,
+    #[serde(default)]
+    expected_node_id: Option<Box<str>>,
     outputs: Outputs,
 }
 
@@ -35,53 +95,130 @@ impl StorageConfiguration {
     pub async fn create_instance(
         self,
         instance_name: Box<str>,
+        InstanceSettings {
+            thresholds,
+            timeouts,
+            mode,
+        }: InstanceSettings,
+        service: &ServiceConfiguration,
     ) -> Result<Instances<Instance<Configuration, State>>> {
-        let json_rpc = http_client()?;
+        let json_rpc = http_client(timeouts)?;
 
-        let url = self.json_rpc_url.clone().join("/status")?;
+        let url = join_relative(
+            &self.json_rpc_url,
+            self.status_path.as_deref().unwrap_or(DEFAULT_STATUS_PATH),
+        )?;
 
-        let last_block = State::fetch_status(&json_rpc, url.clone())
-            .await?
-            .latest_block_height();
+        let (last_block, result) = match mode {
+            CheckMode::Continuous => {
+                match State::fetch_status(&json_rpc, url.clone()).await {
+                    Ok(response) => (
+                        Some(response.latest_block_height()),
+                        Err(Failure::new(
+                            FailureReason::Stalled,
+                            "Awaiting a block newer than the one observed \
+                            during initialization.",
+                        )),
+                    ),
+                    Err(error) => {
+                        let failure = http_client::failure(error);
 
-        let enabled = Status::Disabled;
+                        warn!(
+                            "Failed to fetch status of node at {url} during \
+                            initialization! Starting it as down with an \
+                            unknown block height. Cause: {failure}",
+                        );
+
+                        (None, Err(failure))
+                    }
+                }
+            }
+            CheckMode::OneShot => (None, Err(CheckMode::not_checked_yet())),
+        };
 
         let mutable = Mutex::new(StateInnerMutable {
             last_block,
+            last_advance: Instant::now(),
+            stalled_cycles: 0,
+            reported_block: None,
             epoch: Epoch::new(),
-            status: enabled,
+            result: result.clone(),
+            block_lag: Ok(()),
+            fetch_duration: Duration::ZERO,
         });
 
-        let state = State(Arc::new(StateInner {
-            json_rpc,
+        let node = Arc::new(StateInner {
+            json_rpc: json_rpc.clone(),
             url,
+            max_block_lag: service.max_block_lag,
+            max_block_age: service.max_block_age,
+            stall_tolerance: service.stall_tolerance,
+            require_progress: mode == CheckMode::Continuous,
+            expected_chain_id: self
+                .expected_chain_id
+                .or_else(|| service.chain_id.clone()),
+            expected_node_id: self.expected_node_id,
             mutable,
-        }));
+        });
+
+        let lcd_endpoint = self
+            .lcd_url
+            .map(|url| join_relative(&url, LCD_SYNCING_PATH))
+            .transpose()?
+            .map(|url| Endpoint::Lcd {
+                client: json_rpc,
+                url,
+            });
+
+        let grpc_endpoint = self
+            .grpc_url
+            .map(|url| -> Result<_> {
+                Ok(Endpoint::Grpc {
+                    client: grpc_client(timeouts)?,
+                    url: join_relative(&url, GRPC_SYNCING_PATH)?,
+                })
+            })
+            .transpose()?;
 
         Ok(Instances {
             lcd: Instance::new(
                 instance_name.clone(),
-                enabled,
+                result.clone(),
+                thresholds,
                 Configuration {
                     output: self.outputs.lcd,
                 },
-                state.clone(),
+                State {
+                    node: node.clone(),
+                    endpoint: lcd_endpoint,
+                    check_duration: None,
+                },
             ),
             json_rpc: Instance::new(
                 instance_name.clone(),
-                enabled,
+                result.clone(),
+                thresholds,
                 Configuration {
                     output: self.outputs.json_rpc,
                 },
-                state.clone(),
+                State {
+                    node: node.clone(),
+                    endpoint: None,
+                    check_duration: None,
+                },
             ),
             grpc: Instance::new(
                 instance_name,
-                enabled,
+                result,
+                thresholds,
                 Configuration {
                     output: self.outputs.grpc,
                 },
-                state.clone(),
+                State {
+                    node,
+                    endpoint: grpc_endpoint,
+                    check_duration: None,
+                },
             ),
         })
     }
@@ -110,14 +247,17 @@ impl service::Configuration for Configuration {
 }
 
 #[derive(Clone)]
-#[repr(transparent)]
-pub(crate) struct State(Arc<StateInner>);
+pub(crate) struct State {
+    node: Arc<StateInner>,
+    endpoint: Option<Endpoint>,
+    check_duration: Option<Duration>,
+}
 
 impl State {
     async fn fetch_status(
         json_rpc: &ReqwestClient,
         url: Url,
-    ) -> Result<StatusResponse> {
+    ) -> Result<StatusResponse, ReqwestError> {
         json_rpc
             .get(url)
             .send()
@@ -125,70 +265,414 @@ impl State {
             .and_then(ReqwestResponse::error_for_status)?
             .json()
             .await
-            .map_err(From::from)
     }
 }
 
-impl service::Healthcheck for State {
-    async fn healthcheck(
-        &mut self,
+impl State {
+    async fn node_healthcheck(
+        &self,
         epoch: Epoch,
-        output_verbosity: OutputVerbosity<InstanceName<'_>>,
-    ) -> Status {
-        let state = &*self.0;
+    ) -> (Result<(), Failure>, Duration) {
+        let lock = self.poll(epoch).await;
+
+        (
+            lock.result.clone().and_then(|()| lock.block_lag.clone()),
+            lock.fetch_duration,
+        )
+    }
+
+    async fn poll(&self, epoch: Epoch) -> MutexGuard<'_, StateInnerMutable> {
+        let state = &*self.node;
 
         let mut lock = state.mutable.lock().await;
 
         if lock.epoch != epoch {
-            lock.status =
-                Self::fetch_status(&state.json_rpc, state.url.clone())
-                    .await
-                    .map_or(
-                        Status::Disabled,
-                        #[inline]
-                        |response| {
-                            let latest_block_height =
-                                response.latest_block_height();
-
-                            if lock.last_block < latest_block_height {
-                                lock.last_block = latest_block_height;
-
-                                (!response.catching_up()).into()
-                            } else {
-                                Status::Disabled
-                            }
-                        },
-                    );
+            lock.reported_block = None;
+
+            let started_at = Instant::now();
+
+            let response =
+                Self::fetch_status(&state.json_rpc, state.url.clone()).await;
+
+            lock.fetch_duration = started_at.elapsed();
+
+            let response = match response {
+                Ok(response) => state
+                    .check_identity(&response)
+                    .map(|()| response)
+                    .inspect_err(|failure| {
+                        if lock.result.as_ref().err().map(Failure::reason)
+                            != Some(FailureReason::IdentityMismatch)
+                        {
+                            warn!(
+                                "Node at {url} failed identity validation! \
+                                Cause: {failure}",
+                                url = state.url,
+                            );
+                        }
+                    }),
+                Err(error) => Err(http_client::failure(error)),
+            };
+
+            lock.result = match (response, lock.last_block) {
+                (Ok(response), None) => {
+                    let latest_block_height = response.latest_block_height();
+
+                    lock.reported_block = Some(latest_block_height);
+
+                    lock.last_block = Some(latest_block_height);
+
+                    lock.last_advance = Instant::now();
+
+                    if state.require_progress {
+                        Err(Failure::new(
+                            FailureReason::Stalled,
+                            format!(
+                                "Awaiting a block newer than \
+                                {latest_block_height}, the first one observed.",
+                            ),
+                        ))
+                    } else {
+                        state.check_synced(&response)
+                    }
+                }
+                (Ok(response), Some(last_block)) => {
+                    let latest_block_height = response.latest_block_height();
+
+                    lock.reported_block = Some(latest_block_height);
+
+                    let advanced = last_block < latest_block_height;
+
+                    if advanced {
+                        lock.last_block = Some(latest_block_height);
+
+                        lock.last_advance = Instant::now();
+
+                        lock.stalled_cycles = 0;
+                    } else {
+                        lock.stalled_cycles =
+                            lock.stalled_cycles.saturating_add(1);
+                    }
+
+                    if advanced
+                        || !state.require_progress
+                        || state.stall_tolerated(&lock)
+                    {
+                        state.check_synced(&response)
+                    } else {
+                        Err(Failure::new(
+                            FailureReason::Stalled,
+                            format!(
+                                "Latest block height, {latest_block_height}, \
+                                did not advance past {last_block}.",
+                            ),
+                        ))
+                    }
+                }
+                (Err(failure), _) => Err(failure),
+            };
 
             lock.epoch = epoch;
         };
 
-        if let OutputVerbosity::Verbose(InstanceName {
-            service_name,
-            instance_name,
-        }) = output_verbosity
-        {
-            info!(
-                "[{service_name:?}; {instance_name:?}] is {status}.",
-                status = match lock.status {
-                    Status::Disabled => "DOWN",
-                    Status::Enabled => "UP",
-                }
-            );
+        lock
+    }
+
+    async fn apply_block_lag(&self, reported_block: Option<u64>, highest: u64) {
+        let Some(max_block_lag) = self.node.max_block_lag else {
+            return;
+        };
+
+        self.node.mutable.lock().await.block_lag = match reported_block {
+            Some(reported_block)
+                if highest - reported_block > max_block_lag =>
+            {
+                Err(Failure::new(
+                    FailureReason::BlockLag,
+                    format!(
+                        "Latest block height, {reported_block}, is {} blocks \
+                        behind the highest reported height, {highest}.",
+                        highest - reported_block,
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        };
+    }
+}
+
+pub(crate) async fn check_block_lag(
+    instances: &[Instance<Configuration, State>],
+    epoch: Epoch,
+) {
+    let reported_blocks = join_all(instances.iter().map(|instance| async {
+        instance.state().poll(epoch).await.reported_block
+    }))
+    .await;
+
+    let Some(highest) = reported_blocks.iter().flatten().max().copied() else {
+        return;
+    };
+
+    join_all(instances.iter().zip(reported_blocks).map(
+        |(instance, reported_block)| {
+            instance.state().apply_block_lag(reported_block, highest)
+        },
+    ))
+    .await;
+}
+
+impl service::Healthcheck for State {
+    async fn healthcheck(
+        &mut self,
+        epoch: Epoch,
+        output_verbosity: OutputVerbosity<InstanceName<'_>>,
+    ) -> Result<(), Failure> {
+        let (mut result, mut check_duration) =
+            self.node_healthcheck(epoch).await;
+
+        if let (Ok(()), Some(endpoint)) = (&result, &self.endpoint) {
+            let started_at = Instant::now();
+
+            result = endpoint.healthcheck().await;
+
+            check_duration += started_at.elapsed();
+        }
+
+        self.check_duration = Some(check_duration);
+
+        output_verbosity.report(&result);
+
+        result
+    }
+
+    #[inline]
+    fn same_target(&self, previous: &Self) -> bool {
+        self.node.same_checks(&previous.node)
+            && self.endpoint.as_ref().map(Endpoint::url)
+                == previous.endpoint.as_ref().map(Endpoint::url)
+    }
+
+    async fn carry_over(&mut self, previous: &Self) {
+        if Arc::ptr_eq(&self.node, &previous.node) {
+            return;
+        }
+
+        let mut lock = self.node.mutable.lock().await;
+
+        let previous = previous.node.mutable.lock().await;
+
+        if lock.last_block <= previous.last_block {
+            lock.last_block = previous.last_block;
+
+            lock.last_advance = previous.last_advance;
+
+            lock.stalled_cycles = previous.stalled_cycles;
+        }
+
+        lock.result.clone_from(&previous.result);
+    }
+
+    #[inline]
+    fn check_duration(&self) -> Option<Duration> {
+        self.check_duration
+    }
+
+    async fn block_height(&self) -> Option<u64> {
+        self.node.mutable.lock().await.last_block
+    }
+}
+
+#[derive(Clone)]
+enum Endpoint {
+    Lcd { client: ReqwestClient, url: Url },
+    Grpc { client: ReqwestClient, url: Url },
+}
+
+impl Endpoint {
+    #[inline]
+    const fn url(&self) -> &Url {
+        match self {
+            Self::Lcd { url, .. } | Self::Grpc { url, .. } => url,
         }
+    }
+
+    async fn healthcheck(&self) -> Result<(), Failure> {
+        let syncing = match self {
+            Self::Lcd { client, url } => client
+                .get(url.clone())
+                .send()
+                .await
+                .and_then(ReqwestResponse::error_for_status)
+                .map_err(http_client::failure)?
+                .json::<SyncingResponse>()
+                .await
+                .map_err(http_client::failure)?
+                .syncing(),
+            Self::Grpc { client, url } => {
+                grpc::unary(client, url.clone(), &grpc::encode_request(&[]))
+                    .await
+                    .and_then(|response| grpc::varint_field(&response, 1))?
+                    .is_some_and(|syncing| syncing != 0)
+            }
+        };
 
-        lock.status
+        if syncing {
+            Err(Failure::new(
+                FailureReason::CatchingUp,
+                "Endpoint reports that the node is syncing.",
+            ))
+        } else {
+            Ok(())
+        }
     }
 }
 
 struct StateInner {
     json_rpc: ReqwestClient,
     url: Url,
+    max_block_lag: Option<u64>,
+    max_block_age: Option<Duration>,
+    stall_tolerance: Option<StallTolerance>,
+    require_progress: bool,
+    expected_chain_id: Option<Box<str>>,
+    expected_node_id: Option<Box<str>>,
     mutable: Mutex<StateInnerMutable>,
 }
 
+impl StateInner {
+    fn same_checks(&self, previous: &Self) -> bool {
+        self.url == previous.url
+            && self.max_block_age == previous.max_block_age
+            && self.stall_tolerance == previous.stall_tolerance
+            && self.expected_chain_id == previous.expected_chain_id
+            && self.expected_node_id == previous.expected_node_id
+    }
+
+    fn check_identity(&self, response: &StatusResponse) -> Result<(), Failure> {
+        let failure = if let Some(expected_chain_id) = self
+            .expected_chain_id
+            .as_deref()
+            .filter(|&expected_chain_id| {
+                response.network() != Some(expected_chain_id)
+            }) {
+            response.network().map_or_else(
+                || {
+                    format!(
+                        "Node does not report a chain ID, while \
+                        {expected_chain_id:?} is expected.",
+                    )
+                },
+                |network| {
+                    format!(
+                        "Node reports chain ID {network:?}, while \
+                        {expected_chain_id:?} is expected.",
+                    )
+                },
+            )
+        } else if let Some(expected_node_id) =
+            self.expected_node_id.as_deref().filter(|expected_node_id| {
+                !response.node_id().is_some_and(|node_id| {
+                    expected_node_id.eq_ignore_ascii_case(node_id)
+                })
+            })
+        {
+            response.node_id().map_or_else(
+                || {
+                    format!(
+                        "Node does not report a node ID, while \
+                        {expected_node_id:?} is expected.",
+                    )
+                },
+                |node_id| {
+                    format!(
+                        "Node reports node ID {node_id:?}, while \
+                        {expected_node_id:?} is expected.",
+                    )
+                },
+            )
+        } else {
+            return Ok(());
+        };
+
+        Err(Failure::new(FailureReason::IdentityMismatch, failure))
+    }
+
+    fn stall_tolerated(&self, mutable: &StateInnerMutable) -> bool {
+        match self.stall_tolerance {
+            None => false,
+            Some(StallTolerance::Cycles(cycles)) => {
+                mutable.stalled_cycles <= cycles
+            }
+            Some(StallTolerance::Seconds(duration)) => {
+                mutable.last_advance.elapsed() <= duration
+            }
+        }
+    }
+
+    fn check_synced(&self, response: &StatusResponse) -> Result<(), Failure> {
+        if response.catching_up() {
+            Err(Failure::new(
+                FailureReason::CatchingUp,
+                "Node is catching up.",
+            ))
+        } else {
+            self.check_block_age(response)
+        }
+    }
+
+    fn check_block_age(
+        &self,
+        response: &StatusResponse,
+    ) -> Result<(), Failure> {
+        let Some(max_block_age) = self.max_block_age else {
+            return Ok(());
+        };
+
+        let latest_block_time =
+            response.latest_block_time().ok_or_else(|| {
+                Failure::new(
+                    FailureReason::Response,
+                    "Status response is missing the latest block time.",
+                )
+            })?;
+
+        let latest_block_time: SystemTime =
+            OffsetDateTime::parse(latest_block_time, &Rfc3339)
+                .map_err(|error| {
+                    Failure::new(
+                        FailureReason::Response,
+                        format!(
+                            "Failed to parse latest block time \
+                            {latest_block_time:?}! Cause: {error}",
+                        ),
+                    )
+                })?
+                .into();
+
+        match SystemTime::now().duration_since(latest_block_time) {
+            Ok(age) if age > max_block_age => Err(Failure::new(
+                FailureReason::StaleBlock,
+                format!(
+                    "Latest block, {}, was produced {} seconds ago, which is \
+                    more than the allowed {} seconds.",
+                    response.latest_block_height(),
+                    age.as_secs(),
+                    max_block_age.as_secs(),
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
 struct StateInnerMutable {
-    last_block: u64,
+    last_block: Option<u64>,
+    last_advance: Instant,
+    stalled_cycles: u32,
+    reported_block: Option<u64>,
     epoch: Epoch,
-    status: Status,
+    result: Result<(), Failure>,
+    block_lag: Result<(), Failure>,
+    fetch_duration: Duration,
 }
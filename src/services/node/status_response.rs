@@ -4,16 +4,34 @@ use serde::{
 };
 
 pub(super) struct StatusResponse {
+    network: Option<Box<str>>,
+    node_id: Option<Box<str>>,
     latest_block_height: u64,
+    latest_block_time: Option<Box<str>>,
     catching_up: bool,
 }
 
 impl StatusResponse {
+    #[inline]
+    pub fn network(&self) -> Option<&str> {
+        self.network.as_deref()
+    }
+
+    #[inline]
+    pub fn node_id(&self) -> Option<&str> {
+        self.node_id.as_deref()
+    }
+
     #[inline]
     pub const fn latest_block_height(&self) -> u64 {
         self.latest_block_height
     }
 
+    #[inline]
+    pub fn latest_block_time(&self) -> Option<&str> {
+        self.latest_block_time.as_deref()
+    }
+
     #[inline]
     pub const fn catching_up(&self) -> bool {
         self.catching_up
@@ -32,12 +50,24 @@ impl<'de> Deserialize<'de> for StatusResponse {
 
         #[derive(Deserialize)]
         struct ResultField {
+            #[serde(default)]
+            node_info: NodeInfoField,
             sync_info: SyncInfoField,
         }
 
+        #[derive(Default, Deserialize)]
+        struct NodeInfoField {
+            #[serde(default)]
+            id: Option<Box<str>>,
+            #[serde(default)]
+            network: Option<Box<str>>,
+        }
+
         #[derive(Deserialize)]
         struct SyncInfoField {
             latest_block_height: Box<str>,
+            #[serde(default)]
+            latest_block_time: Option<Box<str>>,
             catching_up: bool,
         }
 
@@ -46,20 +76,26 @@ impl<'de> Deserialize<'de> for StatusResponse {
             |Root {
                  result:
                      ResultField {
+                         node_info: NodeInfoField { id, network },
                          sync_info:
                              SyncInfoField {
                                  latest_block_height,
+                                 latest_block_time,
                                  catching_up,
                              },
                      },
              }| {
-                latest_block_height
+                let latest_block_height = latest_block_height
                     .parse()
-                    .map(|latest_block_height| Self {
-                        latest_block_height,
-                        catching_up,
-                    })
-                    .map_err(DeserializeError::custom)
+                    .map_err(DeserializeError::custom)?;
+
+                Ok(Self {
+                    network,
+                    node_id: id,
+                    latest_block_height,
+                    latest_block_time,
+                    catching_up,
+                })
             },
         )
     }
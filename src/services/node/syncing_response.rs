@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub(super) struct SyncingResponse {
+    syncing: bool,
+}
+
+impl SyncingResponse {
+    #[inline]
+    pub const fn syncing(&self) -> bool {
+        self.syncing
+    }
+}
@@ -0,0 +1,190 @@
+use anyhow::Result;
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::TcpStream,
+    time::timeout,
+};
+
+use crate::{
+    serde::deserialize_boxed_string,
+    service::{
+        self, Failure, FailureReason, Instance, InstanceName, InstanceSettings,
+        OutputVerbosity, Timeouts,
+    },
+    state::Epoch,
+};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(crate) struct StorageConfiguration {
+    #[serde(deserialize_with = "deserialize_boxed_string")]
+    address: Box<str>,
+    #[serde(default)]
+    send: Option<Box<str>>,
+    #[serde(default)]
+    expect: Option<Box<str>>,
+    #[serde(deserialize_with = "deserialize_boxed_string")]
+    output: Box<str>,
+}
+
+impl StorageConfiguration {
+    pub(crate) async fn create_instance(
+        self,
+        instance_name: Box<str>,
+        InstanceSettings {
+            thresholds,
+            timeouts,
+            mode,
+        }: InstanceSettings,
+    ) -> Result<Instance<Configuration, State>> {
+        let state = State {
+            address: self.address,
+            send: self.send,
+            expect: self.expect,
+            timeouts,
+        };
+
+        Ok(Instance::new(
+            instance_name,
+            mode.initial_result(state.healthcheck()).await,
+            thresholds,
+            Configuration {
+                output: self.output,
+            },
+            state,
+        ))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Configuration {
+    output: Box<str>,
+}
+
+impl service::Configuration for Configuration {
+    fn output(&self) -> &str {
+        &self.output
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct State {
+    address: Box<str>,
+    send: Option<Box<str>>,
+    expect: Option<Box<str>>,
+    timeouts: Timeouts,
+}
+
+impl State {
+    async fn healthcheck(&self) -> Result<(), Failure> {
+        timeout(self.timeouts.request, self.exchange())
+            .await
+            .unwrap_or_else(|_| {
+                Err(Failure::new(
+                    FailureReason::Timeout,
+                    format!(
+                        "Healthcheck of {:?} did not complete within {} \
+                        seconds.",
+                        self.address,
+                        self.timeouts.request.as_secs(),
+                    ),
+                ))
+            })
+    }
+
+    async fn exchange(&self) -> Result<(), Failure> {
+        let mut stream =
+            timeout(self.timeouts.connect, TcpStream::connect(&*self.address))
+                .await
+                .map_err(|_| {
+                    Failure::new(
+                        FailureReason::Timeout,
+                        format!(
+                            "Connecting to {:?} did not complete within {} \
+                            seconds.",
+                            self.address,
+                            self.timeouts.connect.as_secs(),
+                        ),
+                    )
+                })?
+                .map_err(|error| {
+                    Failure::new(
+                        FailureReason::Connect,
+                        format!(
+                            "Failed to connect to {:?}: {error}",
+                            self.address,
+                        ),
+                    )
+                })?;
+
+        if let Some(send) = &self.send {
+            stream.write_all(send.as_bytes()).await.map_err(|error| {
+                Failure::new(
+                    FailureReason::Request,
+                    format!("Failed to send request: {error}"),
+                )
+            })?;
+        }
+
+        if let Some(expect) = &self.expect {
+            let mut received = vec![0; expect.len()];
+
+            let mut length = 0;
+
+            while length < received.len() {
+                match stream.read(&mut received[length..]).await {
+                    Ok(0) => break,
+                    Ok(read) => length += read,
+                    Err(error) => {
+                        return Err(Failure::new(
+                            FailureReason::Response,
+                            format!("Failed to read response: {error}"),
+                        ));
+                    }
+                }
+            }
+
+            received.truncate(length);
+
+            if *received != *expect.as_bytes() {
+                return Err(Failure::new(
+                    FailureReason::Response,
+                    format!(
+                        "Expected response starting with {:?}, but received \
+                        {:?}.",
+                        expect,
+                        String::from_utf8_lossy(&received),
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl service::Healthcheck for State {
+    #[inline]
+    async fn healthcheck(
+        &mut self,
+        _: Epoch,
+        output_verbosity: OutputVerbosity<InstanceName<'_>>,
+    ) -> Result<(), Failure> {
+        let result = Self::healthcheck(self).await;
+
+        output_verbosity.report(&result);
+
+        result
+    }
+
+    #[inline]
+    fn same_target(&self, previous: &Self) -> bool {
+        self.address == previous.address
+            && self.send == previous.send
+            && self.expect == previous.expect
+    }
+
+    #[inline]
+    async fn carry_over(&mut self, _: &Self) {}
+}
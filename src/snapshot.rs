@@ -0,0 +1,58 @@
+use std::{path::Path, time::SystemTime};
+
+use crate::service::{Failure, ManualOverride, Statistics, Status};
+
+#[derive(Default)]
+pub(crate) struct Snapshot {
+    pub services: Box<[ServiceSnapshot]>,
+    pub configuration_reloads: ReloadCounters,
+    pub nginx_reloads: ReloadCounters,
+}
+
+pub(crate) struct ServiceSnapshot {
+    pub service_name: Box<str>,
+    pub instances: Box<[InstanceSnapshot]>,
+}
+
+impl ServiceSnapshot {
+    pub fn healthy_instances(&self) -> usize {
+        self.instances
+            .iter()
+            .filter(|instance| matches!(instance.status, Status::Enabled))
+            .count()
+    }
+}
+
+pub(crate) struct InstanceSnapshot {
+    pub instance_name: Box<str>,
+    pub status: Status,
+    pub manual_override: Option<ManualOverride>,
+    pub maintenance_file: Option<Box<Path>>,
+    pub last_failure: Option<Failure>,
+    pub consecutive_successes: u32,
+    pub consecutive_failures: u32,
+    pub statistics: Statistics,
+    pub block_height: Option<u64>,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ReloadCounters {
+    pub successful: u64,
+    pub failed: u64,
+    pub last_succeeded: Option<bool>,
+    pub last_success_at: Option<SystemTime>,
+}
+
+impl ReloadCounters {
+    pub fn register(&mut self, succeeded: bool) {
+        if succeeded {
+            self.successful += 1;
+
+            self.last_success_at = Some(SystemTime::now());
+        } else {
+            self.failed += 1;
+        }
+
+        self.last_succeeded = Some(succeeded);
+    }
+}
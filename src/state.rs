@@ -1,19 +1,27 @@
-use std::{path::Path, process::Command, time::Duration};
+use std::{
+    net::SocketAddr,
+    path::Path,
+    time::{Duration, SystemTime},
+};
 
-use anyhow::{anyhow, Context as _, Result};
-use futures::{stream::FuturesUnordered, StreamExt as _};
+use anyhow::{Context as _, Result};
+use futures::{future::join_all, stream::FuturesUnordered, StreamExt as _};
 use tokio::{
     fs::{self, File},
     io::AsyncWriteExt as _,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
+    command::Command,
     configuration::Configuration,
+    output_file,
+    reload::Reload,
     service::{
-        self, OutputVerbosity, Service, ServiceName, Services, StateChange,
-        WriteOutStatus,
+        self, CheckMode, ManualOverride, OutputVerbosity, Service, ServiceName,
+        Services, StateChange, Timeouts, WriteOutStatus,
     },
+    snapshot::{ReloadCounters, ServiceSnapshot, Snapshot},
     writer,
 };
 
@@ -32,13 +40,38 @@ impl Epoch {
     }
 }
 
+#[derive(Default)]
+struct Reloads {
+    counters: ReloadCounters,
+    last_error: Option<ReloadError>,
+}
+
+struct ReloadError {
+    occurred_at: SystemTime,
+    error: Box<str>,
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum WriteOutOutcome {
+    Written,
+    ValidationRejected,
+    ReloadFailed,
+}
+
 pub(super) struct State {
     static_configuration: Box<[u8]>,
     refresh_period: Duration,
     verbose_output: bool,
     global_prepend: Box<str>,
+    validation: Option<Command>,
+    reload: Reload,
+    listen_address: Option<SocketAddr>,
+    admin_listen_address: Option<SocketAddr>,
     epoch: Epoch,
     services: Services,
+    output_pending: bool,
+    reloads: Reloads,
+    nginx_reloads: ReloadCounters,
 }
 
 impl State {
@@ -55,12 +88,23 @@ impl State {
             refresh_period,
             verbose_output,
             prepend: global_prepend,
+            timeout,
+            connect_timeout,
+            validation,
+            reload,
+            listen_address,
+            admin_listen_address,
             services,
         } = Self::load_services_configuration(services_configuration).await?;
 
         info!("Preparing service clients.");
 
-        service::from_configurations(services)
+        let timeouts = Timeouts {
+            request: timeout,
+            connect: connect_timeout,
+        };
+
+        service::from_configurations(services, timeouts, CheckMode::Continuous)
             .await
             .inspect(|_| {
                 info!("Prepared service clients.");
@@ -76,11 +120,77 @@ impl State {
                 refresh_period,
                 verbose_output,
                 global_prepend,
+                validation,
+                reload,
+                listen_address,
+                admin_listen_address,
                 epoch: Epoch::new(),
                 services,
+                output_pending: false,
+                reloads: Reloads::default(),
+                nginx_reloads: ReloadCounters::default(),
             })
     }
 
+    pub async fn reload(
+        &mut self,
+        static_configuration: &Path,
+        services_configuration: &Path,
+    ) -> bool {
+        match Self::load(static_configuration, services_configuration).await {
+            Ok(mut state) => {
+                let carried_over =
+                    service::carry_over(&mut state.services, &self.services)
+                        .await;
+
+                info!(
+                    "Carried over health state of {carried_over} unchanged \
+                    instance{}.",
+                    if carried_over == 1 { "" } else { "s" },
+                );
+
+                let mut counters = self.reloads.counters;
+
+                counters.register(true);
+
+                state.reloads = Reloads {
+                    counters,
+                    last_error: None,
+                };
+
+                state.nginx_reloads = self.nginx_reloads;
+
+                *self = state;
+
+                info!(
+                    successful_reloads = self.reloads.counters.successful,
+                    failed_reloads = self.reloads.counters.failed,
+                    "Configuration reloaded.",
+                );
+
+                true
+            }
+            Err(error) => {
+                self.reloads.counters.register(false);
+
+                self.reloads.last_error = Some(ReloadError {
+                    occurred_at: SystemTime::now(),
+                    error: format!("{error:#}").into_boxed_str(),
+                });
+
+                error!(
+                    ?error,
+                    successful_reloads = self.reloads.counters.successful,
+                    failed_reloads = self.reloads.counters.failed,
+                    "Configuration reload rejected! Continuing with the \
+                    previous configuration. Cause: {error:#}",
+                );
+
+                false
+            }
+        }
+    }
+
     async fn load_static_configuration(
         static_configuration: &Path,
     ) -> Result<Box<[u8]>> {
@@ -122,7 +232,6 @@ impl State {
 
         serde_json::from_reader(&*services_json)
             .context("Failed to parse services configuration!")
-            .map_err(From::from)
     }
 
     #[inline]
@@ -130,18 +239,99 @@ impl State {
         self.refresh_period
     }
 
+    #[inline]
+    pub const fn listen_address(&self) -> Option<SocketAddr> {
+        self.listen_address
+    }
+
+    #[inline]
+    pub const fn admin_listen_address(&self) -> Option<SocketAddr> {
+        self.admin_listen_address
+    }
+
     pub async fn output_configuration(
         &mut self,
         output_configuration: &Path,
         forced: bool,
     ) -> Result<()> {
-        let updated = self
-            .services
+        let updated = Self::healthcheck_services(
+            &mut self.services,
+            self.epoch,
+            self.verbose_output,
+        )
+        .await;
+
+        self.epoch.start_new_epoch();
+
+        if forced
+            || self.output_pending
+            || matches!(updated, StateChange::Changed)
+        {
+            self.write_out_configuration(output_configuration).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn check(
+        services_configuration: &Path,
+    ) -> Result<Box<[ServiceSnapshot]>> {
+        let Configuration {
+            verbose_output,
+            timeout,
+            connect_timeout,
+            services,
+            ..
+        } = Self::load_services_configuration(services_configuration).await?;
+
+        info!("Preparing service clients.");
+
+        let mut services = service::from_configurations(
+            services,
+            Timeouts {
+                request: timeout,
+                connect: connect_timeout,
+            },
+            CheckMode::OneShot,
+        )
+        .await
+        .context("Failed to prepare service clients!")?;
+
+        info!("Prepared service clients. Running healthchecks.");
+
+        let mut epoch = Epoch::new();
+
+        epoch.start_new_epoch();
+
+        Self::healthcheck_services(&mut services, epoch, verbose_output).await;
+
+        Ok(Self::snapshot_services(&services).await)
+    }
+
+    async fn healthcheck_services(
+        services: &mut Services,
+        epoch: Epoch,
+        verbose_output: bool,
+    ) -> StateChange {
+        let now = SystemTime::now();
+
+        let overridden = services
+            .iter_mut()
+            .map(|(service_name, service)| {
+                service.refresh_overrides(service_name, now)
+            })
+            .collect::<FuturesUnordered<_>>()
+            .fold(StateChange::Unchanged, |accumulated, service| async move {
+                accumulated & service
+            })
+            .await;
+
+        let updated = services
             .iter_mut()
             .map(|(service_name, service)| {
                 service.healthcheck(
-                    self.epoch,
-                    if self.verbose_output {
+                    epoch,
+                    if verbose_output {
                         OutputVerbosity::Verbose(ServiceName { service_name })
                     } else {
                         OutputVerbosity::Standard
@@ -154,37 +344,159 @@ impl State {
             })
             .await;
 
-        self.epoch.start_new_epoch();
+        overridden & updated
+    }
 
-        if forced || matches!(updated, StateChange::Changed) {
-            self.write_out_services(output_configuration).await?;
-
-            if Command::new("systemctl")
-                .arg("reload")
-                .arg("nginx.service")
-                .status()
-                .context(
-                    "Failed to invoke Systemd reload command for `nginx`!",
-                )?
-                .success()
-            {
-                Ok(())
-            } else {
-                Err(anyhow!(
-                    "Systemd reload command for `nginx` exited with an error!"
-                ))
-            }
-        } else {
-            Ok(())
+    pub fn set_manual_override(
+        &mut self,
+        service_name: &str,
+        instance_name: &str,
+        manual_override: Option<ManualOverride>,
+    ) -> bool {
+        let found =
+            self.services.get_mut(service_name).is_some_and(|service| {
+                service.set_manual_override(
+                    service_name,
+                    instance_name,
+                    manual_override,
+                )
+            });
+
+        if found {
+            self.output_pending = true;
         }
+
+        found
     }
 
-    async fn write_out_services(
+    pub async fn write_out_configuration(
         &mut self,
         output_configuration: &Path,
+    ) -> Result<WriteOutOutcome> {
+        self.output_pending = true;
+
+        if let Some(ReloadError { occurred_at, error }) =
+            &self.reloads.last_error
+        {
+            warn!(
+                seconds_since_rejection =
+                    occurred_at.elapsed().unwrap_or_default().as_secs(),
+                failed_reloads = self.reloads.counters.failed,
+                "Writing out output configuration based on the \
+                configuration preceding the last, rejected, reload! \
+                Cause: {error}",
+            );
+        }
+
+        let mut candidate =
+            output_file::Candidate::create(output_configuration).await?;
+
+        if let Err(error) = self.write_out_services(&mut candidate).await {
+            candidate.discard().await;
+
+            return Err(error);
+        }
+
+        if !self.validate(candidate.path()).await {
+            candidate.discard().await;
+
+            return Ok(WriteOutOutcome::ValidationRejected);
+        }
+
+        candidate.commit(output_configuration).await?;
+
+        self.output_pending = !self.reload_nginx().await;
+
+        Ok(if self.output_pending {
+            WriteOutOutcome::ReloadFailed
+        } else {
+            WriteOutOutcome::Written
+        })
+    }
+
+    async fn reload_nginx(&mut self) -> bool {
+        info!("Reloading NGINX.");
+
+        let succeeded = self
+            .reload
+            .run()
+            .await
+            .inspect(|()| {
+                info!("Reloaded NGINX.");
+            })
+            .inspect_err(|error| {
+                error!(?error, "Failed to reload NGINX! Cause: {error}");
+            })
+            .is_ok();
+
+        self.nginx_reloads.register(succeeded);
+
+        succeeded
+    }
+
+    pub async fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            services: Self::snapshot_services(&self.services).await,
+            configuration_reloads: self.reloads.counters,
+            nginx_reloads: self.nginx_reloads,
+        }
+    }
+
+    async fn snapshot_services(services: &Services) -> Box<[ServiceSnapshot]> {
+        join_all(services.iter().map(|(service_name, service)| async move {
+            ServiceSnapshot {
+                service_name: service_name.clone(),
+                instances: service.snapshot().await,
+            }
+        }))
+        .await
+        .into_boxed_slice()
+    }
+
+    async fn validate(&self, candidate_configuration: &Path) -> bool {
+        let Some(validation) = &self.validation else {
+            return true;
+        };
+
+        info!("Validating candidate output configuration.");
+
+        match validation
+            .run(&[(Command::CANDIDATE_PLACEHOLDER, candidate_configuration)])
+            .await
+        {
+            Ok(output) if validation.succeeded(output.status) => {
+                info!("Candidate output configuration passed validation.");
+
+                true
+            }
+            Ok(output) => {
+                error!(
+                    status = %output.status,
+                    stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                    "Candidate output configuration failed validation! \
+                    Keeping previous output configuration.",
+                );
+
+                false
+            }
+            Err(error) => {
+                error!(
+                    ?error,
+                    "Failed to run validation of candidate output \
+                    configuration! Keeping previous output configuration. \
+                    Cause: {error}",
+                );
+
+                false
+            }
+        }
+    }
+
+    async fn write_out_services(
+        &self,
+        candidate: &mut output_file::Candidate,
     ) -> Result<()> {
-        let mut output_configuration =
-            File::create(output_configuration).await?;
+        let output_configuration = candidate.file();
 
         output_configuration
             .write_all(&self.static_configuration)
@@ -192,7 +504,7 @@ impl State {
 
         for (service_name, service) in &self.services {
             Self::write_out_service(
-                &mut output_configuration,
+                output_configuration,
                 &self.global_prepend,
                 service_name,
                 service,
@@ -202,7 +514,7 @@ impl State {
 
         info!("");
 
-        Ok(())
+        candidate.sync().await
     }
 
     async fn write_out_service(
@@ -219,7 +531,10 @@ impl State {
 
         output_configuration.write_all(b" {\n").await?;
 
-        let WriteOutStatus { healthy_instances } = service
+        let WriteOutStatus {
+            healthy_instances,
+            fallback,
+        } = service
             .write_out(
                 writer::UpstreamSectionEntry::new(&mut *output_configuration),
                 global_prepend,
@@ -231,6 +546,13 @@ impl State {
             if healthy_instances == 1 { "" } else { "s" }
         );
 
+        if let Some(fallback) = fallback {
+            warn!(
+                "{service_name:?} has no healthy instances! Falling back to \
+                {fallback}.",
+            );
+        }
+
         output_configuration
             .write_all(b"}\n")
             .await
@@ -0,0 +1,210 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{ser::Error as _, Serialize, Serializer};
+use time::{
+    format_description::well_known::Rfc3339, Duration as TimeDuration,
+    OffsetDateTime,
+};
+
+use crate::{
+    service::{Failure, ManualOverride, Status},
+    snapshot::{InstanceSnapshot, ReloadCounters, ServiceSnapshot, Snapshot},
+};
+
+pub(crate) fn render(snapshot: &Snapshot) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&StatusResponse::new(snapshot))
+}
+
+pub(crate) fn render_services(
+    services: &[ServiceSnapshot],
+) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&ServicesStatus::new(services))
+}
+
+#[derive(Serialize)]
+struct StatusResponse<'r> {
+    #[serde(flatten)]
+    services: ServicesStatus<'r>,
+    configuration_reloads: ReloadsStatus,
+    nginx_reloads: ReloadsStatus,
+}
+
+impl<'r> StatusResponse<'r> {
+    fn new(snapshot: &'r Snapshot) -> Self {
+        Self {
+            services: ServicesStatus::new(&snapshot.services),
+            configuration_reloads: ReloadsStatus::new(
+                &snapshot.configuration_reloads,
+            ),
+            nginx_reloads: ReloadsStatus::new(&snapshot.nginx_reloads),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ServicesStatus<'r> {
+    services: BTreeMap<&'r str, ServiceStatus<'r>>,
+}
+
+impl<'r> ServicesStatus<'r> {
+    fn new(services: &'r [ServiceSnapshot]) -> Self {
+        Self {
+            services: services
+                .iter()
+                .map(|service| {
+                    (
+                        &*service.service_name,
+                        ServiceStatus {
+                            healthy_instances: service.healthy_instances(),
+                            instances: service
+                                .instances
+                                .iter()
+                                .map(InstanceStatus::new)
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ServiceStatus<'r> {
+    healthy_instances: usize,
+    instances: Box<[InstanceStatus<'r>]>,
+}
+
+#[derive(Serialize)]
+struct InstanceStatus<'r> {
+    name: &'r str,
+    #[serde(serialize_with = "serialize_status")]
+    status: Status,
+    manual_override: Option<ManualOverrideStatus>,
+    maintenance_file: Option<&'r Path>,
+    #[serde(serialize_with = "serialize_optional_time")]
+    last_checked_at: Option<SystemTime>,
+    last_latency_seconds: Option<f64>,
+    consecutive_successes: u32,
+    consecutive_failures: u32,
+    last_failure: Option<FailureStatus<'r>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_height: Option<u64>,
+}
+
+impl<'r> InstanceStatus<'r> {
+    fn new(instance: &'r InstanceSnapshot) -> Self {
+        let last_check = instance.statistics.last_check();
+
+        Self {
+            name: &instance.instance_name,
+            status: instance.status,
+            manual_override: instance
+                .manual_override
+                .as_ref()
+                .map(ManualOverrideStatus::new),
+            maintenance_file: instance.maintenance_file.as_deref(),
+            last_checked_at: last_check.map(|last_check| last_check.checked_at),
+            last_latency_seconds: last_check
+                .map(|last_check| last_check.latency.as_secs_f64()),
+            consecutive_successes: instance.consecutive_successes,
+            consecutive_failures: instance.consecutive_failures,
+            last_failure: instance
+                .last_failure
+                .as_ref()
+                .map(FailureStatus::new),
+            block_height: instance.block_height,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ManualOverrideStatus {
+    mode: &'static str,
+    #[serde(serialize_with = "serialize_optional_time")]
+    expires_at: Option<SystemTime>,
+}
+
+impl ManualOverrideStatus {
+    const fn new(manual_override: &ManualOverride) -> Self {
+        Self {
+            mode: manual_override.mode.as_str(),
+            expires_at: manual_override.expires_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FailureStatus<'r> {
+    reason: &'static str,
+    details: &'r str,
+}
+
+impl<'r> FailureStatus<'r> {
+    fn new(failure: &'r Failure) -> Self {
+        Self {
+            reason: failure.reason().as_str(),
+            details: failure.details(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ReloadsStatus {
+    successful: u64,
+    failed: u64,
+    last_succeeded: Option<bool>,
+    #[serde(serialize_with = "serialize_optional_time")]
+    last_success_at: Option<SystemTime>,
+}
+
+impl ReloadsStatus {
+    const fn new(counters: &ReloadCounters) -> Self {
+        Self {
+            successful: counters.successful,
+            failed: counters.failed,
+            last_succeeded: counters.last_succeeded,
+            last_success_at: counters.last_success_at,
+        }
+    }
+}
+
+fn serialize_status<S>(
+    status: &Status,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(status.as_str())
+}
+
+fn serialize_optional_time<S>(
+    time: &Option<SystemTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let Some(time) = time else {
+        return serializer.serialize_none();
+    };
+
+    let since_epoch = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(S::Error::custom)
+        .and_then(|since_epoch| {
+            TimeDuration::try_from(since_epoch).map_err(S::Error::custom)
+        })?;
+
+    OffsetDateTime::UNIX_EPOCH
+        .checked_add(since_epoch)
+        .ok_or_else(|| S::Error::custom("time is out of range"))?
+        .format(&Rfc3339)
+        .map_err(S::Error::custom)
+        .and_then(|time| serializer.serialize_str(&time))
+}
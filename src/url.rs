@@ -1,3 +1,4 @@
+use anyhow::Result as AnyhowResult;
 use reqwest::Url;
 use serde::de::{Deserialize as _, Deserializer, Error as _};
 
@@ -8,3 +9,25 @@ where
     String::deserialize(deserializer)
         .and_then(|url| url.parse().map_err(D::Error::custom))
 }
+
+pub(crate) fn deserialize_optional<'de, D>(
+    deserializer: D,
+) -> Result<Option<Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).and_then(|url| {
+        url.map(|url| url.parse().map_err(D::Error::custom))
+            .transpose()
+    })
+}
+
+pub(crate) fn join_relative(base: &Url, path: &str) -> AnyhowResult<Url> {
+    let mut base = base.clone();
+
+    if !base.path().ends_with('/') {
+        base.set_path(&format!("{}/", base.path()));
+    }
+
+    base.join(path).map_err(From::from)
+}
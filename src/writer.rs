@@ -2,7 +2,7 @@ use anyhow::Result;
 
 use tokio::io::AsyncWriteExt;
 
-use crate::service::ServiceOutputWriter;
+use crate::service::{EntryState, ServiceOutputWriter};
 
 #[repr(transparent)]
 pub(super) struct UpstreamSectionEntry<T>(T);
@@ -25,6 +25,27 @@ where
 
         buf.write_all(b";\n").await.map_err(From::from)
     }
+
+    async fn write_down(
+        buf: &mut T,
+        output: &str,
+        comment: Option<String>,
+    ) -> Result<()> {
+        buf.write_all(b"    ").await?;
+
+        buf.write_all(mark_down(output).as_bytes()).await?;
+
+        buf.write_all(b";").await?;
+
+        if let Some(comment) = comment {
+            buf.write_all(b" ").await?;
+
+            buf.write_all(comment.replace(['\r', '\n'], " ").as_bytes())
+                .await?;
+        }
+
+        buf.write_all(b"\n").await.map_err(From::from)
+    }
 }
 
 impl<T> ServiceOutputWriter for UpstreamSectionEntry<T>
@@ -38,7 +59,97 @@ where
         Self::write_terminated(&mut self.0, output).await
     }
 
-    async fn write_out_entry<'r>(&'r mut self, output: &'r str) -> Result<()> {
-        Self::write_terminated(&mut self.0, output).await
+    async fn write_out_entry<'r>(
+        &'r mut self,
+        output: &'r str,
+        state: EntryState<'r>,
+    ) -> Result<()> {
+        match state {
+            EntryState::Up => Self::write_terminated(&mut self.0, output).await,
+            EntryState::Down(failure) => {
+                Self::write_down(
+                    &mut self.0,
+                    output,
+                    failure.map(|failure| format!("# {failure}")),
+                )
+                .await
+            }
+            EntryState::Overridden(mode) => {
+                Self::write_down(
+                    &mut self.0,
+                    output,
+                    Some(format!("# manual override: {mode}")),
+                )
+                .await
+            }
+            EntryState::Maintenance(file) => {
+                Self::write_down(
+                    &mut self.0,
+                    output,
+                    Some(format!(
+                        "# maintenance file present: {}",
+                        file.display(),
+                    )),
+                )
+                .await
+            }
+        }
+    }
+}
+
+fn mark_down(output: &str) -> String {
+    output
+        .split(';')
+        .map(|statement| {
+            let statement = statement.trim_end();
+
+            let is_server = statement
+                .trim_start()
+                .strip_prefix("server")
+                .is_some_and(|rest| rest.starts_with(char::is_whitespace));
+
+            if is_server {
+                format!("{statement} down")
+            } else {
+                statement.into()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mark_down;
+
+    #[test]
+    fn marks_single_server_down() {
+        assert_eq!(
+            mark_down("server 127.0.0.1:80"),
+            "server 127.0.0.1:80 down"
+        );
+
+        assert_eq!(
+            mark_down("server 127.0.0.1:80 weight=2"),
+            "server 127.0.0.1:80 weight=2 down",
+        );
+    }
+
+    #[test]
+    fn marks_every_server_down() {
+        assert_eq!(
+            mark_down("server 127.0.0.1:80; server 127.0.0.2:80 backup"),
+            "server 127.0.0.1:80 down; server 127.0.0.2:80 backup down",
+        );
+    }
+
+    #[test]
+    fn keeps_other_statements() {
+        assert_eq!(
+            mark_down("keepalive 16; server 127.0.0.1:80"),
+            "keepalive 16; server 127.0.0.1:80 down",
+        );
+
+        assert_eq!(mark_down("servers 1"), "servers 1");
     }
 }